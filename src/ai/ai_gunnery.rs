@@ -0,0 +1,124 @@
+use super::Ai;
+use crate::{
+    SystemUpdateSet,
+    player::Player,
+    rotation,
+    ship_composition::{
+        ammo::ReloadState,
+        bullet::BulletAssets,
+        countermeasure::PointDefense,
+        gun::Gun,
+        turret::{self, Turret},
+    },
+    velocity::global::GlobalVelocity,
+};
+use bevy::prelude::*;
+
+pub struct AiGunneryPlugin {}
+
+impl Plugin for AiGunneryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                ai_aim.before(turret::traverse_turrets),
+                ai_shoot.after(turret::traverse_turrets),
+            )
+                .in_set(SystemUpdateSet::Main),
+        );
+    }
+}
+
+/// ai only opens fire once the player is this close
+const FIRE_RANGE: f32 = 250.;
+/// radians a fixed gun can point away from the lead and still fire
+const FIXED_GUN_TOLERANCE: f32 = 0.1;
+
+/// point ai turrets ahead of the player
+fn ai_aim(
+    ai_query: Query<&Children, With<Ai>>,
+    player_query: Query<(&GlobalTransform, &GlobalVelocity), With<Player>>,
+    mut gun_query: Query<
+        (&mut Turret, &Gun, &GlobalTransform, &GlobalVelocity),
+        Without<PointDefense>,
+    >,
+) {
+    let Ok((p_g_transform, p_g_velocity)) = player_query.single() else {
+        return;
+    };
+    let p_position = p_g_transform.translation().xy();
+
+    for children in ai_query {
+        for &child in children {
+            let Ok((mut turret, gun, g_transform, g_velocity)) = gun_query.get_mut(child) else {
+                continue;
+            };
+
+            turret.aim_point = Some(
+                turret::lead_target(
+                    g_transform.translation().xy(),
+                    g_velocity.0,
+                    p_position,
+                    p_g_velocity.0,
+                    gun.bullet_data().speed,
+                )
+                .unwrap_or(p_position),
+            );
+        }
+    }
+}
+
+/// fire ai guns once on target and in range, and reload while the player is out of range
+/// turrets aim themselves, fixed guns wait for the ship to point them ahead of the player
+fn ai_shoot(
+    ai_query: Query<(Entity, &GlobalTransform, &Children), With<Ai>>,
+    player_query: Query<(&GlobalTransform, &GlobalVelocity), With<Player>>,
+    mut gun_query: Query<
+        (Option<&Turret>, &mut Gun, &Transform, &GlobalVelocity),
+        Without<PointDefense>,
+    >,
+    bullet_assets: Res<BulletAssets>,
+    mut commands: Commands,
+) {
+    let Ok((p_g_transform, p_g_velocity)) = player_query.single() else {
+        return;
+    };
+    let p_position = p_g_transform.translation().xy();
+
+    for (ai, a_g_transform, children) in ai_query {
+        for &child in children {
+            let Ok((opt_turret, mut gun, transform, g_velocity)) = gun_query.get_mut(child) else {
+                continue;
+            };
+            let g_transform = turret::current_global_transform(a_g_transform, transform);
+
+            let position = g_transform.translation().xy();
+            if position.distance(p_position) > FIRE_RANGE {
+                // nothing to shoot at, top up the magazine while there's time
+                if gun.reload_state() == ReloadState::Ready {
+                    gun.reload();
+                }
+                continue;
+            }
+
+            let on_target = match opt_turret {
+                Some(turret) => turret.on_target(),
+                None => {
+                    let aim_point = turret::lead_target(
+                        position,
+                        g_velocity.0,
+                        p_position,
+                        p_g_velocity.0,
+                        gun.bullet_data().speed,
+                    )
+                    .unwrap_or(p_position);
+                    let facing = rotation::quat_to_vec2(g_transform.rotation());
+                    facing.angle_to(aim_point - position).abs() <= FIXED_GUN_TOLERANCE
+                }
+            };
+            if on_target {
+                gun.try_shoot(&ai, &mut commands, &g_transform, g_velocity, &bullet_assets);
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use ai_ship_tree::AiShipTreePlugin;
 use bevy::prelude::*;
+mod ai_gunnery;
 mod ai_ship_tree;
 mod setup;
 
@@ -7,7 +8,11 @@ pub struct AiPlugin {}
 
 impl Plugin for AiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((setup::SetupPlugin {}, AiShipTreePlugin {}));
+        app.add_plugins((
+            setup::SetupPlugin {},
+            AiShipTreePlugin {},
+            ai_gunnery::AiGunneryPlugin {},
+        ));
     }
 }
 
@@ -1,3 +1,4 @@
+use super::Ai;
 use crate::{
     AppState,
     data_config::{
@@ -8,7 +9,6 @@ use crate::{
     velocity::{AngularVelocity, Velocity},
 };
 use bevy::prelude::*;
-use bevy_behave::prelude::BehaveTree;
 
 pub struct SetupPlugin {}
 
@@ -25,7 +25,9 @@ fn setup(
     data_registry: Res<DataRegistry>,
     data_table: Res<Assets<DataTable>>,
 ) {
-    ship::spawn_ship_from_blueprint(
+    let mut ai_ships = Vec::new();
+
+    ai_ships.extend(ship::spawn_ship_from_blueprint(
         "ship_2",
         &BlueprintType::TransformVelocity(
             Transform::from_translation(Vec3::new(-200., 0., 0.)),
@@ -37,9 +39,9 @@ fn setup(
         &data_registry,
         &data_table,
         &mut commands,
-    );
+    ));
 
-    ship::spawn_ship_from_blueprint(
+    ai_ships.extend(ship::spawn_ship_from_blueprint(
         "ship_2",
         &BlueprintType::TransformVelocity(
             Transform::from_translation(Vec3::new(200., 0., 0.)),
@@ -51,7 +53,7 @@ fn setup(
         &data_registry,
         &data_table,
         &mut commands,
-    );
+    ));
 
     if let Some(ai_ship) = ship::spawn_ship_from_blueprint(
         "ship_1",
@@ -67,5 +69,10 @@ fn setup(
         &mut commands,
     ) {
         super::ai_ship_tree::add_ai_ship_tree(ai_ship, &mut commands, false);
+        ai_ships.push(ai_ship);
+    }
+
+    for ai_ship in ai_ships {
+        commands.entity(ai_ship).insert(Ai {});
     }
 }
@@ -0,0 +1,202 @@
+use bevy::{
+    math::bounding::Aabb2d,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+
+/// colliders covering more cells than this skip the grid and get tested against everything
+/// (i.e., huge sonar pulses or planets)
+const MAX_CELLS_PER_COLLIDER: i32 = 1024;
+
+/// tweakable settings of the collision broadphase
+#[derive(Resource, Reflect, Debug, Clone)]
+pub struct BroadphaseSettings {
+    /// width and height of one grid cell, in world units
+    /// works best when a bit bigger than most colliders
+    pub cell_size: f32,
+}
+
+impl Default for BroadphaseSettings {
+    fn default() -> Self {
+        BroadphaseSettings { cell_size: 50. }
+    }
+}
+
+/// uniform grid mapping each cell to the colliders whose AABB touches it
+/// rebuilt every frame, so only colliders sharing a cell reach the narrow phase
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<usize>>,
+    /// colliders too big to be worth putting into cells
+    oversized: Vec<usize>,
+    len: usize,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        SpatialHash {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// first and last cell (inclusive) the AABB covers
+    fn cell_range(&self, aabb: &Aabb2d) -> (IVec2, IVec2) {
+        let min = (aabb.min / self.cell_size).floor().as_ivec2();
+        let max = (aabb.max / self.cell_size).floor().as_ivec2();
+        (min, max)
+    }
+
+    /// add collider with the given index, covering every cell its global AABB touches
+    pub fn insert(&mut self, index: usize, aabb: &Aabb2d) {
+        self.len = self.len.max(index + 1);
+
+        let (min, max) = self.cell_range(aabb);
+        // huge AABBs saturate to i32::MIN/MAX cells, so the span itself can overflow
+        let width = max.x.saturating_sub(min.x).saturating_add(1);
+        let height = max.y.saturating_sub(min.y).saturating_add(1);
+        let cell_count = width.saturating_mul(height);
+        if cell_count > MAX_CELLS_PER_COLLIDER || cell_count <= 0 {
+            self.oversized.push(index);
+            return;
+        }
+
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                self.cells.entry(IVec2::new(x, y)).or_default().push(index);
+            }
+        }
+    }
+
+    /// every unique pair (i < j) of colliders that may be touching
+    /// sorted, so pairs come out in the same order as `Query::iter_combinations`
+    pub fn candidate_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = HashSet::new();
+
+        for indexes in self.cells.values() {
+            for (n, &a) in indexes.iter().enumerate() {
+                for &b in &indexes[n + 1..] {
+                    pairs.insert((a.min(b), a.max(b)));
+                }
+            }
+        }
+
+        for &big in &self.oversized {
+            for other in 0..self.len {
+                if other != big {
+                    pairs.insert((big.min(other), big.max(other)));
+                }
+            }
+        }
+
+        let mut pairs: Vec<(usize, usize)> = pairs.into_iter().collect();
+        pairs.sort_unstable();
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpatialHash;
+    use bevy::math::bounding::{Aabb2d, IntersectsVolume};
+    use bevy::prelude::*;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use std::time::Instant;
+
+    /// scattered boxes of mixed sizes, up to a bit bigger than a cell
+    fn random_aabbs(count: usize, world_size: f32, seed: u64) -> Vec<Aabb2d> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|_| {
+                let center = Vec2::new(
+                    rng.random_range(-world_size..world_size),
+                    rng.random_range(-world_size..world_size),
+                );
+                let half_size = Vec2::new(rng.random_range(1. ..40.), rng.random_range(1. ..40.));
+                Aabb2d::new(center, half_size)
+            })
+            .collect()
+    }
+
+    fn hash_pairs(aabbs: &[Aabb2d], cell_size: f32) -> Vec<(usize, usize)> {
+        let mut hash = SpatialHash::new(cell_size);
+        for (index, aabb) in aabbs.iter().enumerate() {
+            hash.insert(index, aabb);
+        }
+        hash.candidate_pairs()
+    }
+
+    /// every pair whose AABBs touch, the hard way
+    fn brute_force_pairs(aabbs: &[Aabb2d]) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for (i, aabb1) in aabbs.iter().enumerate() {
+            for (j, aabb2) in aabbs.iter().enumerate().skip(i + 1) {
+                if aabb1.intersects(aabb2) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn finds_every_touching_pair() {
+        let mut aabbs = random_aabbs(500, 1000., 1);
+        // big enough to skip the grid
+        aabbs.push(Aabb2d::new(Vec2::new(300., 0.), Vec2::splat(1000.)));
+        aabbs.push(Aabb2d::new(Vec2::new(-300., 0.), Vec2::splat(1000.)));
+        let candidates = hash_pairs(&aabbs, 50.);
+        for pair in brute_force_pairs(&aabbs) {
+            assert!(
+                candidates.binary_search(&pair).is_ok(),
+                "missed pair {pair:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn pairs_are_unique_and_sorted() {
+        let aabbs = random_aabbs(200, 300., 2);
+        let candidates = hash_pairs(&aabbs, 50.);
+        assert!(candidates.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(candidates.iter().all(|(i, j)| i < j));
+    }
+
+    #[test]
+    fn huge_aabb_pairs_with_everything() {
+        let mut aabbs = random_aabbs(50, 1000., 3);
+        aabbs.push(Aabb2d::new(Vec2::ZERO, Vec2::splat(f32::MAX)));
+        let big = aabbs.len() - 1;
+        let candidates = hash_pairs(&aabbs, 50.);
+        for other in 0..big {
+            assert!(candidates.contains(&(other, big)));
+        }
+    }
+
+    /// run with `cargo test --release -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn benchmark_against_brute_force() {
+        let aabbs = random_aabbs(5000, 5000., 4);
+
+        let start = Instant::now();
+        let candidates = hash_pairs(&aabbs, 50.);
+        let hash_time = start.elapsed();
+
+        let start = Instant::now();
+        let touching = brute_force_pairs(&aabbs);
+        let brute_force_time = start.elapsed();
+
+        println!(
+            "{} colliders: spatial hash {:?} ({} candidate pairs), brute force {:?} ({} touching pairs)",
+            aabbs.len(),
+            hash_time,
+            candidates.len(),
+            brute_force_time,
+            touching.len(),
+        );
+        assert!(candidates.len() >= touching.len());
+    }
+}
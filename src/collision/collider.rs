@@ -1,51 +1,106 @@
 use super::collider_type::ColliderType;
+use crate::data_config::collision_matrix::CollisionMatrix;
 use bevy::prelude::*;
 use serde::Deserialize;
+use std::borrow::Cow;
 
 #[derive(Component, Clone, Reflect)]
 pub struct Collider {
     pub bounding: ColliderType,
     pub collision_layer: CollisionLayer,
-    pub collides_with: Vec<CollisionLayer>,
+    /// overrides which layers this collider hits, instead of using the CollisionMatrix
+    pub collides_with: Option<Vec<CollisionLayer>>,
+    /// swept against others using its last recorded position, so fast movers don't tunnel
+    /// needs Record<GlobalTransform> to work
+    pub continuous: bool,
+    /// how the collider physically reacts when hitting others
+    /// None means it only sends events
+    pub response: Option<CollisionResponse>,
 }
 
 impl Collider {
     pub fn new(bounding: ColliderType, collision_layer: CollisionLayer) -> Self {
         Collider {
             bounding,
-            collides_with: collision_layer.collides_with(),
+            collides_with: None,
             collision_layer,
+            continuous: false,
+            response: None,
         }
     }
 
+    /// bounce off and get damaged by other colliders that also have a response
+    pub fn with_response(mut self, response: CollisionResponse) -> Self {
+        self.response = Some(response);
+        self
+    }
+
+    /// opt into continuous collision detection
+    pub fn with_continuous(mut self) -> Self {
+        self.continuous = true;
+        self
+    }
+
+    /// only collide with these layers, no matter what the CollisionMatrix says
+    pub fn with_collides_with(mut self, layers: Vec<CollisionLayer>) -> Self {
+        self.collides_with = Some(layers);
+        self
+    }
+
     /// makes sure, based on layer masks, that both colliders can in fact collide
-    pub fn can_collide_with(&self, other_layer: &Collider) -> bool {
-        self.collides_with.contains(&other_layer.collision_layer)
-            && other_layer.collides_with.contains(&self.collision_layer)
+    pub fn can_collide_with(&self, other: &Collider, matrix: &CollisionMatrix) -> bool {
+        self.accepts(&other.collision_layer, matrix) && other.accepts(&self.collision_layer, matrix)
+    }
+
+    /// does this collider want to hit the given layer?
+    fn accepts(&self, layer: &CollisionLayer, matrix: &CollisionMatrix) -> bool {
+        match &self.collides_with {
+            Some(layers) => layers.contains(layer),
+            None => matrix.layers_collide(&self.collision_layer, layer),
+        }
     }
 }
 
-#[derive(Reflect, Clone, Deserialize, Debug, PartialEq)]
-pub enum CollisionLayer {
-    Ship,
-    ShipComponent,
-    Bullet,
-    SonarPulse,
-    Planet,
+/// physical properties used when two colliders with responses hit
+#[derive(Reflect, Clone, Deserialize, Debug)]
+pub struct CollisionResponse {
+    /// bounciness, 0 loses all speed along the hit and 1 keeps it all
+    pub restitution: f32,
+    /// how much sliding along the other collider is slowed
+    pub friction: f32,
 }
 
-impl CollisionLayer {
-    fn collides_with(&self) -> Vec<CollisionLayer> {
-        match self {
-            CollisionLayer::Ship => vec![
-                CollisionLayer::Bullet,
-                CollisionLayer::SonarPulse,
-                CollisionLayer::Planet,
-            ],
-            CollisionLayer::ShipComponent => vec![CollisionLayer::Bullet],
-            CollisionLayer::Bullet => vec![CollisionLayer::Ship, CollisionLayer::ShipComponent],
-            CollisionLayer::SonarPulse => vec![CollisionLayer::Ship],
-            CollisionLayer::Planet => vec![CollisionLayer::Ship],
+impl CollisionResponse {
+    pub fn new(restitution: f32, friction: f32) -> Self {
+        CollisionResponse {
+            restitution,
+            friction,
         }
     }
 }
+
+/// name of a collision layer
+/// layers and which ones collide are set in `collision_matrix.ron`
+#[derive(Reflect, Clone, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct CollisionLayer(Cow<'static, str>);
+
+impl CollisionLayer {
+    // layers that are spawned from code
+    pub const SHIP: CollisionLayer = CollisionLayer(Cow::Borrowed("Ship"));
+    pub const SHIP_COMPONENT: CollisionLayer = CollisionLayer(Cow::Borrowed("ShipComponent"));
+    pub const BULLET: CollisionLayer = CollisionLayer(Cow::Borrowed("Bullet"));
+    pub const SONAR_PULSE: CollisionLayer = CollisionLayer(Cow::Borrowed("SonarPulse"));
+    pub const PLANET: CollisionLayer = CollisionLayer(Cow::Borrowed("Planet"));
+    pub const MISSILE: CollisionLayer = CollisionLayer(Cow::Borrowed("Missile"));
+    pub const DECOY: CollisionLayer = CollisionLayer(Cow::Borrowed("Decoy"));
+    pub const FLAK: CollisionLayer = CollisionLayer(Cow::Borrowed("Flak"));
+
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        CollisionLayer(name.into())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
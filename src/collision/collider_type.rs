@@ -1,14 +1,22 @@
+use super::{convex_poly::ConvexPoly, oriented_rect::OrientedRect};
 use crate::primitive::Primitive;
 use bevy::math::bounding::{Aabb2d, BoundingCircle, BoundingVolume};
 use bevy::prelude::*;
 use serde::Deserialize;
 
 #[derive(Reflect, Debug, Deserialize, Clone)]
+#[reflect(no_field_bounds)]
 pub enum ColliderType {
     Rectangle(Aabb2d),
+    /// rectangle that follows the GlobalTransform's rotation
+    OrientedRectangle(OrientedRect),
     Circle(BoundingCircle),
     /// inner, outer
     Ring(BoundingCircle, BoundingCircle),
+    /// follows the GlobalTransform's rotation
+    Polygon(ConvexPoly),
+    /// several shapes acting as one collider
+    Compound(Vec<ColliderType>),
 }
 
 impl ColliderType {
@@ -18,6 +26,14 @@ impl ColliderType {
         ColliderType::Rectangle(Aabb2d::new(Vec2::ZERO, Vec2::new(half_width, half_height)))
     }
 
+    pub fn new_oriented_rect(width: f32, height: f32) -> Self {
+        ColliderType::OrientedRectangle(OrientedRect::new(
+            Vec2::ZERO,
+            Vec2::new(width / 2.0, height / 2.0),
+            0.,
+        ))
+    }
+
     pub fn new_circle(radius: f32) -> Self {
         ColliderType::Circle(BoundingCircle::new(Vec2::ZERO, radius))
     }
@@ -29,23 +45,112 @@ impl ColliderType {
         )
     }
 
-    pub fn convert_to_global(&self, g_translation: Vec3) -> ColliderType {
+    /// smallest axis aligned box containing the whole collider
+    pub fn aabb(&self) -> Aabb2d {
         match self {
-            ColliderType::Rectangle(aabb) => ColliderType::Rectangle(Aabb2d::new(
-                aabb.center() + g_translation.xy(),
-                aabb.half_size(),
-            )),
+            ColliderType::Rectangle(aabb) => *aabb,
+            ColliderType::OrientedRectangle(rect) => rect.aabb(),
+            ColliderType::Circle(circle) => circle.aabb_2d(),
+            ColliderType::Ring(_, outer) => outer.aabb_2d(),
+            ColliderType::Polygon(polygon) => polygon.aabb(),
+            ColliderType::Compound(parts) => parts
+                .iter()
+                .map(ColliderType::aabb)
+                .reduce(|aabb1, aabb2| aabb1.merge(&aabb2))
+                .unwrap_or(Aabb2d::new(Vec2::ZERO, Vec2::ZERO)),
+        }
+    }
+
+    /// vertices (counter clockwise) if collider is a polygon of some sort
+    pub fn polygon(&self) -> Option<Vec<Vec2>> {
+        match self {
+            ColliderType::Rectangle(aabb) => Some(OrientedRect::from_aabb(aabb).corners().to_vec()),
+            ColliderType::OrientedRectangle(rect) => Some(rect.corners().to_vec()),
+            ColliderType::Polygon(polygon) => Some(polygon.vertices.clone()),
+            _ => None,
+        }
+    }
+
+    /// radius of a circle around the AABB center containing the whole collider
+    pub fn bounding_radius(&self) -> f32 {
+        match self {
+            ColliderType::Circle(circle) => circle.radius(),
+            ColliderType::Ring(_, outer) => outer.radius(),
+            _ => self.aabb().half_size().length(),
+        }
+    }
+
+    /// moment of inertia around the collider's center, if it were a solid body of given mass
+    pub fn moment_of_inertia(&self, mass: f32) -> f32 {
+        match self {
+            ColliderType::Rectangle(aabb) => {
+                let size = aabb.half_size() * 2.;
+                mass * size.length_squared() / 12.
+            }
+            ColliderType::OrientedRectangle(rect) => {
+                let size = rect.half_size * 2.;
+                mass * size.length_squared() / 12.
+            }
+            ColliderType::Circle(circle) => mass * circle.radius().powi(2) / 2.,
+            ColliderType::Ring(inner, outer) => {
+                mass * (inner.radius().powi(2) + outer.radius().powi(2)) / 2.
+            }
+            ColliderType::Polygon(polygon) => {
+                // close enough, treat like the polygon's bounding box
+                let size = polygon.aabb().half_size() * 2.;
+                mass * size.length_squared() / 12.
+            }
+            ColliderType::Compound(parts) => {
+                // split mass evenly, then shift each part's inertia to the shared center
+                let center = self.aabb().center();
+                let part_mass = mass / parts.len().max(1) as f32;
+                parts
+                    .iter()
+                    .map(|part| {
+                        part.moment_of_inertia(part_mass)
+                            + part_mass * part.aabb().center().distance_squared(center)
+                    })
+                    .sum()
+            }
+        }
+    }
+
+    /// same collider shifted over by `offset`
+    pub fn translated(&self, offset: Vec2) -> ColliderType {
+        self.convert_to_global(&GlobalTransform::from_translation(offset.extend(0.)))
+    }
+
+    /// moves collider into world space
+    /// Rectangle, Circle and Ring stay axis aligned, only their centers get moved
+    pub fn convert_to_global(&self, g_transform: &GlobalTransform) -> ColliderType {
+        let to_global = |point: Vec2| g_transform.transform_point(point.extend(0.)).xy();
+        match self {
+            ColliderType::Rectangle(aabb) => {
+                ColliderType::Rectangle(Aabb2d::new(to_global(aabb.center()), aabb.half_size()))
+            }
+            ColliderType::OrientedRectangle(rect) => {
+                ColliderType::OrientedRectangle(rect.convert_to_global(g_transform))
+            }
             ColliderType::Circle(circle) => ColliderType::Circle(BoundingCircle::new(
-                circle.center + g_translation.xy(),
+                to_global(circle.center),
                 circle.radius(),
             )),
             ColliderType::Ring(inner, outer) => {
-                let center = inner.center + g_translation.xy();
+                let center = to_global(inner.center);
                 ColliderType::Ring(
                     BoundingCircle::new(center, inner.radius()),
                     BoundingCircle::new(center, outer.radius()),
                 )
             }
+            ColliderType::Polygon(polygon) => {
+                ColliderType::Polygon(polygon.convert_to_global(g_transform))
+            }
+            ColliderType::Compound(parts) => ColliderType::Compound(
+                parts
+                    .iter()
+                    .map(|part| part.convert_to_global(g_transform))
+                    .collect(),
+            ),
         }
     }
 }
@@ -58,6 +163,7 @@ impl From<Primitive> for ColliderType {
             Primitive::Ring(ring) => {
                 ColliderType::new_annulus(ring.inner_circle.radius, ring.outer_circle.radius)
             }
+            Primitive::Polygon(vertices) => ColliderType::Polygon(ConvexPoly::new(vertices)),
         }
     }
 }
@@ -0,0 +1,278 @@
+use super::{collider_type::ColliderType, oriented_rect::OrientedRect, sat};
+use bevy::prelude::*;
+
+/// disk centers closer than this to a polygon edge count as sitting on it
+const ON_EDGE_DISTANCE: f32 = 1e-4;
+
+/// where and how deep two colliders overlap
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Contact {
+    /// world position the colliders touch at
+    pub point: Vec2,
+    /// unit vector pointing from the first collider towards the second
+    pub normal: Vec2,
+    /// how far the colliders overlap along the normal
+    pub depth: f32,
+}
+
+impl Contact {
+    /// same contact, seen from the second collider
+    pub fn flipped(self) -> Self {
+        Contact {
+            normal: -self.normal,
+            ..self
+        }
+    }
+}
+
+/// simplified shapes that contacts are worked out on
+enum ContactShape {
+    Polygon(Vec<Vec2>),
+    Disk {
+        center: Vec2,
+        radius: f32,
+    },
+    Ring {
+        center: Vec2,
+        inner: f32,
+        outer: f32,
+    },
+}
+
+impl ContactShape {
+    fn new(collider: &ColliderType) -> Self {
+        match collider {
+            ColliderType::Rectangle(aabb) => {
+                ContactShape::Polygon(OrientedRect::from_aabb(aabb).corners().to_vec())
+            }
+            ColliderType::OrientedRectangle(rect) => ContactShape::Polygon(rect.corners().to_vec()),
+            ColliderType::Circle(circle) => ContactShape::Disk {
+                center: circle.center,
+                radius: circle.radius(),
+            },
+            ColliderType::Ring(inner, outer) => ContactShape::Ring {
+                center: outer.center,
+                inner: inner.radius(),
+                outer: outer.radius(),
+            },
+            ColliderType::Polygon(polygon) => ContactShape::Polygon(polygon.vertices.clone()),
+            // compounds get split into parts before this, so just use their bounds
+            ColliderType::Compound(_) => {
+                ContactShape::Polygon(OrientedRect::from_aabb(&collider.aabb()).corners().to_vec())
+            }
+        }
+    }
+
+    fn center(&self) -> Vec2 {
+        match self {
+            ContactShape::Polygon(vertices) => polygon_center(vertices),
+            ContactShape::Disk { center, .. } | ContactShape::Ring { center, .. } => *center,
+        }
+    }
+
+    /// point of the shape furthest from `point`
+    fn furthest_from(&self, point: Vec2) -> Vec2 {
+        match self {
+            ContactShape::Polygon(vertices) => vertices
+                .iter()
+                .copied()
+                .max_by(|a, b| {
+                    a.distance_squared(point)
+                        .total_cmp(&b.distance_squared(point))
+                })
+                .unwrap_or(point),
+            ContactShape::Disk {
+                center,
+                radius: outer,
+            }
+            | ContactShape::Ring { center, outer, .. } => {
+                *center + direction(*center, point) * *outer
+            }
+        }
+    }
+}
+
+/// contact between two colliders, assuming they already overlap
+/// normal points from `collider1` towards `collider2`
+/// for compound colliders, uses the deepest contact of the overlapping parts
+pub fn contact(collider1: &ColliderType, collider2: &ColliderType) -> Contact {
+    match (collider1, collider2) {
+        (ColliderType::Compound(parts), other) => deepest(
+            parts
+                .iter()
+                .filter(|part| super::has_collided((*part).clone(), other.clone()))
+                .map(|part| contact(part, other)),
+        ),
+        (other, ColliderType::Compound(parts)) => deepest(
+            parts
+                .iter()
+                .filter(|part| super::has_collided(other.clone(), (*part).clone()))
+                .map(|part| contact(other, part)),
+        ),
+        _ => shape_contact(&ContactShape::new(collider1), &ContactShape::new(collider2)),
+    }
+}
+
+fn deepest(contacts: impl Iterator<Item = Contact>) -> Contact {
+    contacts
+        .max_by(|a, b| a.depth.total_cmp(&b.depth))
+        .unwrap_or_default()
+}
+
+fn shape_contact(shape1: &ContactShape, shape2: &ContactShape) -> Contact {
+    match (shape1, shape2) {
+        (ContactShape::Polygon(vertices1), ContactShape::Polygon(vertices2)) => {
+            polygon_polygon(vertices1, vertices2)
+        }
+        (
+            ContactShape::Disk {
+                center: center1,
+                radius: radius1,
+            },
+            ContactShape::Disk {
+                center: center2,
+                radius: radius2,
+            },
+        ) => disk_disk(*center1, *radius1, *center2, *radius2),
+        (ContactShape::Disk { center, radius }, ContactShape::Polygon(vertices)) => {
+            disk_polygon(*center, *radius, vertices)
+        }
+        (ContactShape::Polygon(vertices), ContactShape::Disk { center, radius }) => {
+            disk_polygon(*center, *radius, vertices).flipped()
+        }
+        (
+            ContactShape::Ring {
+                center,
+                inner,
+                outer,
+            },
+            other,
+        ) => ring_contact(*center, *inner, *outer, other),
+        (
+            other,
+            ContactShape::Ring {
+                center,
+                inner,
+                outer,
+            },
+        ) => ring_contact(*center, *inner, *outer, other).flipped(),
+    }
+}
+
+fn disk_disk(center1: Vec2, radius1: f32, center2: Vec2, radius2: f32) -> Contact {
+    let normal = direction(center2, center1);
+    let depth = (radius1 + radius2 - center1.distance(center2)).max(0.);
+    Contact {
+        point: center1 + normal * (radius1 - depth / 2.),
+        normal,
+        depth,
+    }
+}
+
+fn disk_polygon(center: Vec2, radius: f32, vertices: &[Vec2]) -> Contact {
+    let closest = sat::closest_point_on_polygon(vertices, center);
+    let distance = center.distance(closest);
+
+    if distance <= ON_EDGE_DISTANCE || sat::polygon_contains_point(vertices, center) {
+        // disk center is inside polygon, so has to be pushed out past the closest edge
+        let normal = if distance > ON_EDGE_DISTANCE {
+            direction(center, closest)
+        } else {
+            // center right on the edge has no direction to it, so push away from the middle
+            direction(polygon_center(vertices), center)
+        };
+        return Contact {
+            point: closest,
+            normal,
+            depth: radius + distance,
+        };
+    }
+
+    let normal = direction(closest, center);
+    let depth = (radius - distance).max(0.);
+    Contact {
+        point: closest - normal * depth / 2.,
+        normal,
+        depth,
+    }
+}
+
+/// separating axis with the smallest overlap gives normal and depth
+fn polygon_polygon(vertices1: &[Vec2], vertices2: &[Vec2]) -> Contact {
+    let center_offset = polygon_center(vertices2) - polygon_center(vertices1);
+
+    let mut best_normal = Vec2::X;
+    let mut best_depth = f32::MAX;
+    for axis in sat::axes(vertices1).chain(sat::axes(vertices2)) {
+        let axis = axis.normalize_or_zero();
+        if axis == Vec2::ZERO {
+            continue;
+        }
+        let (min1, max1) = sat::project(vertices1, axis);
+        let (min2, max2) = sat::project(vertices2, axis);
+        let overlap = (max1 - min2).min(max2 - min1);
+        if overlap < best_depth {
+            best_depth = overlap;
+            // point from polygon 1 towards polygon 2
+            best_normal = if center_offset.dot(axis) < 0. {
+                -axis
+            } else {
+                axis
+            };
+        }
+    }
+    let depth = best_depth.max(0.);
+
+    // deepest point of polygon 2 into polygon 1, moved halfway back out
+    let deepest = vertices2
+        .iter()
+        .copied()
+        .min_by(|a, b| a.dot(best_normal).total_cmp(&b.dot(best_normal)))
+        .unwrap_or(Vec2::ZERO);
+    Contact {
+        point: deepest + best_normal * depth / 2.,
+        normal: best_normal,
+        depth,
+    }
+}
+
+/// other shape either sits in the hole and pokes through the inner edge,
+/// or is outside and pokes through the outer edge
+fn ring_contact(center: Vec2, inner: f32, outer: f32, other: &ContactShape) -> Contact {
+    let other_center = other.center();
+
+    if center.distance(other_center) < inner {
+        // in the hole, so ring pushes other towards ring center
+        let furthest = other.furthest_from(center);
+        let dir = direction(furthest, center);
+        let depth = (furthest.distance(center) - inner).max(0.);
+        return Contact {
+            point: center + dir * (inner + depth / 2.),
+            normal: -dir,
+            depth,
+        };
+    }
+
+    // outside the hole, so ring acts like a solid disk
+    let disk = ContactShape::Disk {
+        center,
+        radius: outer,
+    };
+    match other {
+        ContactShape::Ring {
+            center: other_center,
+            outer: other_outer,
+            ..
+        } => disk_disk(center, outer, *other_center, *other_outer),
+        other => shape_contact(&disk, other),
+    }
+}
+
+/// unit vector from `from` to `to`, or X if they're on top of each other
+fn direction(to: Vec2, from: Vec2) -> Vec2 {
+    (to - from).try_normalize().unwrap_or(Vec2::X)
+}
+
+fn polygon_center(vertices: &[Vec2]) -> Vec2 {
+    vertices.iter().sum::<Vec2>() / vertices.len().max(1) as f32
+}
@@ -0,0 +1,163 @@
+use bevy::{math::bounding::Aabb2d, prelude::*};
+use serde::{Deserialize, Deserializer, de::Error};
+use std::f32::consts::TAU;
+
+/// convex polygon, vertices kept counter clockwise
+/// NOTE: concave shapes should be split up into a compound collider
+#[derive(Reflect, Debug, Deserialize, Clone)]
+#[serde(try_from = "Vec<Vec2>")]
+pub struct ConvexPoly {
+    pub vertices: Vec<Vec2>,
+}
+
+impl TryFrom<Vec<Vec2>> for ConvexPoly {
+    type Error = String;
+
+    fn try_from(vertices: Vec<Vec2>) -> Result<Self, Self::Error> {
+        check_convex(&vertices)?;
+        Ok(ConvexPoly::new(vertices))
+    }
+}
+
+impl ConvexPoly {
+    pub fn new(mut vertices: Vec<Vec2>) -> Self {
+        if signed_area(&vertices) < 0. {
+            vertices.reverse();
+        }
+        ConvexPoly { vertices }
+    }
+
+    /// polygon moved and rotated into world space
+    pub fn convert_to_global(&self, g_transform: &GlobalTransform) -> Self {
+        ConvexPoly {
+            vertices: self
+                .vertices
+                .iter()
+                .map(|vertex| g_transform.transform_point(vertex.extend(0.)).xy())
+                .collect(),
+        }
+    }
+
+    pub fn aabb(&self) -> Aabb2d {
+        let min = self.vertices.iter().copied().reduce(Vec2::min);
+        let max = self.vertices.iter().copied().reduce(Vec2::max);
+        Aabb2d {
+            min: min.unwrap_or_default(),
+            max: max.unwrap_or_default(),
+        }
+    }
+
+    pub fn area(&self) -> f32 {
+        signed_area(&self.vertices).abs()
+    }
+
+    /// average of vertices
+    pub fn center(&self) -> Vec2 {
+        self.vertices.iter().sum::<Vec2>() / self.vertices.len().max(1) as f32
+    }
+}
+
+/// positive if vertices go counter clockwise
+pub fn signed_area(vertices: &[Vec2]) -> f32 {
+    (0..vertices.len())
+        .map(|i| vertices[i].perp_dot(vertices[(i + 1) % vertices.len()]))
+        .sum::<f32>()
+        / 2.
+}
+
+/// errors if the vertices, going either way round, don't make a convex polygon
+pub fn check_convex(vertices: &[Vec2]) -> Result<(), String> {
+    let count = vertices.len();
+    if count < 3 {
+        return Err(format!("polygon needs at least 3 vertices, got {count}"));
+    }
+
+    let edges: Vec<Vec2> = (0..count)
+        .map(|i| vertices[(i + 1) % count] - vertices[i])
+        .collect();
+    if edges.contains(&Vec2::ZERO) {
+        return Err(format!("polygon has a repeated vertex: {vertices:?}"));
+    }
+
+    let winding = signed_area(vertices);
+    if winding == 0. {
+        return Err(format!("polygon has no area: {vertices:?}"));
+    }
+
+    // every corner has to turn the same way the polygon winds
+    let concave = (0..count).any(|i| edges[i].perp_dot(edges[(i + 1) % count]) * winding < 0.);
+    // stars turn the same way at every corner, but go round more than once
+    let total_turn: f32 = (0..count)
+        .map(|i| edges[i].angle_to(edges[(i + 1) % count]))
+        .sum();
+    if concave || (total_turn.abs() - TAU).abs() > 0.01 {
+        return Err(format!("polygon isn't convex: {vertices:?}"));
+    }
+    Ok(())
+}
+
+/// for `#[serde(deserialize_with)]`, rejects vertices that aren't a convex polygon
+pub fn deserialize_vertices<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Vec2>, D::Error> {
+    let vertices = Vec::<Vec2>::deserialize(deserializer)?;
+    check_convex(&vertices).map_err(D::Error::custom)?;
+    Ok(vertices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConvexPoly, signed_area};
+    use crate::{collision::collider_type::ColliderType, primitive::Primitive};
+
+    fn parse(vertices: &str) -> Result<ConvexPoly, ron::error::SpannedError> {
+        ron::from_str(vertices)
+    }
+
+    #[test]
+    fn deserialized_clockwise_polygon_is_wound_counter_clockwise() {
+        let polygon = parse("[(-10., -5.), (-10., 5.), (10., 0.)]").unwrap();
+        assert!(signed_area(&polygon.vertices) > 0.);
+
+        let polygon = parse("[(-10., -5.), (10., 0.), (-10., 5.)]").unwrap();
+        assert!(signed_area(&polygon.vertices) > 0.);
+    }
+
+    #[test]
+    fn collinear_vertices_are_allowed() {
+        assert!(parse("[(0., 0.), (1., 0.), (2., 0.), (2., 2.), (0., 2.)]").is_ok());
+    }
+
+    #[test]
+    fn rejects_concave_polygon() {
+        // arrow head, with a notch at the back
+        assert!(parse("[(0., 0.), (2., -2.), (1., 0.), (2., 2.)]").is_err());
+    }
+
+    #[test]
+    fn rejects_star() {
+        // pentagram, every corner turns the same way
+        assert!(
+            parse("[(0., 1.), (0.59, -0.81), (-0.95, 0.31), (0.95, 0.31), (-0.59, -0.81)]")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_degenerate_polygons() {
+        assert!(parse("[(0., 0.), (1., 0.)]").is_err());
+        assert!(parse("[(0., 0.), (1., 0.), (2., 0.)]").is_err());
+        assert!(parse("[(0., 0.), (1., 0.), (1., 0.), (0., 1.)]").is_err());
+    }
+
+    #[test]
+    fn collider_and_primitive_polygons_are_checked() {
+        let ship = "Polygon([(-10., -5.), (10., 0.), (-10., 5.)])";
+        assert!(ron::from_str::<ColliderType>(ship).is_ok());
+        assert!(ron::from_str::<Primitive>(ship).is_ok());
+
+        let concave = "Polygon([(0., 0.), (2., -2.), (1., 0.), (2., 2.)])";
+        assert!(ron::from_str::<ColliderType>(concave).is_err());
+        assert!(ron::from_str::<Primitive>(concave).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use super::{CollisionEvent, contact::Contact};
+use bevy::{platform::collections::HashSet, prelude::*};
+
+/// two colliders started touching this frame
+/// contact normal points from the first entity towards the second
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CollisionStarted(pub Entity, pub Entity, pub Contact);
+
+/// two colliders that were touching last frame are still touching
+/// contact normal points from the first entity towards the second
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CollisionOngoing(pub Entity, pub Entity, pub Contact);
+
+/// two colliders that were touching last frame stopped touching (or one was despawned)
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CollisionEnded(pub Entity, pub Entity);
+
+impl CollisionStarted {
+    /// the entity touching the given one, None if entity isn't part of the collision
+    pub fn other(&self, entity: Entity) -> Option<Entity> {
+        other_entity((self.0, self.1), entity)
+    }
+}
+
+impl CollisionOngoing {
+    /// the entity touching the given one, None if entity isn't part of the collision
+    pub fn other(&self, entity: Entity) -> Option<Entity> {
+        other_entity((self.0, self.1), entity)
+    }
+}
+
+impl CollisionEnded {
+    /// the entity that stopped touching the given one, None if entity isn't part of the collision
+    pub fn other(&self, entity: Entity) -> Option<Entity> {
+        other_entity((self.0, self.1), entity)
+    }
+}
+
+fn other_entity((entity1, entity2): (Entity, Entity), entity: Entity) -> Option<Entity> {
+    if entity == entity1 {
+        Some(entity2)
+    } else if entity == entity2 {
+        Some(entity1)
+    } else {
+        None
+    }
+}
+
+/// every pair of colliders touching as of the last collision check
+#[derive(Resource, Default, Debug)]
+pub struct CollidingPairs {
+    /// smaller entity always first, so each pair is only stored once
+    pairs: HashSet<(Entity, Entity)>,
+}
+
+impl CollidingPairs {
+    pub fn are_touching(&self, entity1: Entity, entity2: Entity) -> bool {
+        self.pairs.contains(&ordered(entity1, entity2))
+    }
+
+    /// every entity currently touching the given one
+    pub fn touching(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.pairs
+            .iter()
+            .filter_map(move |&pair| other_entity(pair, entity))
+    }
+}
+
+fn ordered(entity1: Entity, entity2: Entity) -> (Entity, Entity) {
+    (entity1.min(entity2), entity1.max(entity2))
+}
+
+/// compares this frame's collisions against last frame's to find which pairs started,
+/// kept, or stopped touching
+pub fn track_collision_pairs(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut colliding_pairs: ResMut<CollidingPairs>,
+    mut started_events: EventWriter<CollisionStarted>,
+    mut ongoing_events: EventWriter<CollisionOngoing>,
+    mut ended_events: EventWriter<CollisionEnded>,
+) {
+    let mut pairs = HashSet::new();
+
+    for collision in collision_events.read() {
+        let (entity1, entity2, contact) = (collision.0, collision.1, collision.2);
+        if !pairs.insert(ordered(entity1, entity2)) {
+            // pair already handled this frame
+            continue;
+        }
+
+        if colliding_pairs.are_touching(entity1, entity2) {
+            ongoing_events.write(CollisionOngoing(entity1, entity2, contact));
+        } else {
+            started_events.write(CollisionStarted(entity1, entity2, contact));
+        }
+    }
+
+    for &(entity1, entity2) in colliding_pairs.pairs.difference(&pairs) {
+        ended_events.write(CollisionEnded(entity1, entity2));
+    }
+
+    colliding_pairs.pairs = pairs;
+}
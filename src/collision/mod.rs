@@ -1,24 +1,74 @@
-use crate::SystemUpdateSet;
-use bevy::{ecs::query::QueryFilter, math::bounding::IntersectsVolume, prelude::*};
+use crate::{
+    SystemUpdateSet, data_config::collision_matrix::CollisionMatrix, debug, record::Record,
+    schedule::UpdateSchedule,
+};
+use bevy::{
+    ecs::query::QueryFilter,
+    math::bounding::{BoundingCircle, BoundingVolume, IntersectsVolume},
+    prelude::*,
+};
+pub mod broadphase;
+use broadphase::{BroadphaseSettings, SpatialHash};
 pub mod collider;
 use collider::Collider;
 pub mod collider_type;
 use collider_type::ColliderType;
+pub mod contact;
+use contact::Contact;
+pub mod convex_poly;
+pub mod lifecycle;
+use lifecycle::{CollidingPairs, CollisionEnded, CollisionOngoing, CollisionStarted};
+pub mod oriented_rect;
+pub mod query;
+pub mod response;
+use response::ResponseSettings;
+mod sat;
+mod swept;
 
 pub struct CollisionPlugin {}
 
 impl Plugin for CollisionPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<CollisionEvent>()
-            .add_systems(Update, determine_collisions.in_set(SystemUpdateSet::Main))
+            .add_event::<CollisionStarted>()
+            .add_event::<CollisionOngoing>()
+            .add_event::<CollisionEnded>()
+            .init_resource::<CollidingPairs>()
+            .add_systems(
+                Update,
+                (
+                    determine_collisions,
+                    response::resolve_collisions.after(determine_collisions),
+                    lifecycle::track_collision_pairs.after(determine_collisions),
+                )
+                    .in_set(SystemUpdateSet::Main),
+            )
             .register_type::<Collider>();
+
+        debug::insert_inspectable_resource::<BroadphaseSettings>(app, None, false);
+        debug::insert_inspectable_resource::<ResponseSettings>(app, None, false);
     }
 }
 
+/// two colliders touched (sent every frame they touch, see `lifecycle` for when they start or
+/// stop touching)
+/// contact normal points from the first entity towards the second
 #[derive(Event)]
-pub struct CollisionEvent(pub Entity, pub Entity);
+pub struct CollisionEvent(pub Entity, pub Entity, pub Contact);
 
 impl CollisionEvent {
+    /// contact as seen from the given entity, so normal points away from it
+    /// None if entity isn't part of the collision
+    pub fn contact_for(&self, entity: Entity) -> Option<Contact> {
+        if entity == self.0 {
+            Some(self.2)
+        } else if entity == self.1 {
+            Some(self.2.flipped())
+        } else {
+            None
+        }
+    }
+
     /// determines if any of the colliding entities has requested component
     pub fn has_component<T: Component>(&self, world: &World) -> (bool, bool) {
         (
@@ -36,34 +86,126 @@ impl CollisionEvent {
     }
 }
 
+/// collider moved into world space, along with how far it moved since last frame
+struct GlobalCollider<'a> {
+    collider: &'a Collider,
+    bounding: ColliderType,
+    /// zero unless collider is continuous
+    motion: Vec2,
+    entity: Entity,
+}
+
 fn determine_collisions(
-    query: Query<(&Collider, &GlobalTransform, Entity)>,
+    query: Query<(
+        &Collider,
+        &GlobalTransform,
+        Entity,
+        Option<&Record<GlobalTransform>>,
+    )>,
+    settings: Res<BroadphaseSettings>,
+    matrix: Res<CollisionMatrix>,
     mut events: EventWriter<CollisionEvent>,
 ) {
-    for [
-        (collider1, g_transform_1, entity1),
-        (collider2, g_transform_2, entity2),
-    ] in query.iter_combinations()
-    {
-        if !collider1.can_collide_with(collider2) {
+    let colliders: Vec<GlobalCollider> = query
+        .iter()
+        .map(|(collider, g_transform, entity, opt_record)| {
+            let last_position = opt_record
+                .filter(|_| collider.continuous)
+                .and_then(|record| record.deq(&UpdateSchedule::Update).front())
+                .map(|entry| entry.val.translation().xy());
+
+            GlobalCollider {
+                collider,
+                bounding: collider.bounding.convert_to_global(g_transform),
+                motion: last_position
+                    .map_or(Vec2::ZERO, |last| g_transform.translation().xy() - last),
+                entity,
+            }
+        })
+        .collect();
+
+    // broadphase: only colliders sharing a grid cell can be touching
+    let mut spatial_hash = SpatialHash::new(settings.cell_size);
+    for (index, g_collider) in colliders.iter().enumerate() {
+        spatial_hash.insert(
+            index,
+            &swept::swept_aabb(&g_collider.bounding, g_collider.motion),
+        );
+    }
+
+    for (i, j) in spatial_hash.candidate_pairs() {
+        let (g_collider_1, g_collider_2) = (&colliders[i], &colliders[j]);
+
+        if !g_collider_1
+            .collider
+            .can_collide_with(g_collider_2.collider, &matrix)
+        {
             // the collision layers cannot collide
             continue;
         }
-        if has_collided(
-            collider1
-                .bounding
-                .convert_to_global(g_transform_1.translation()),
-            collider2
-                .bounding
-                .convert_to_global(g_transform_2.translation()),
-        ) {
-            events.write(CollisionEvent(entity1, entity2));
+        let contact = if has_collided(g_collider_1.bounding.clone(), g_collider_2.bounding.clone())
+        {
+            Some(contact::contact(
+                &g_collider_1.bounding,
+                &g_collider_2.bounding,
+            ))
+        } else {
+            swept_contact(g_collider_1, g_collider_2)
+        };
+
+        if let Some(contact) = contact {
+            events.write(CollisionEvent(
+                g_collider_1.entity,
+                g_collider_2.entity,
+                contact,
+            ));
         }
     }
 }
 
+/// did the colliders touch at any point during their motion this frame?
+/// sweeps the smaller collider against the other using their relative motion, and gives the
+/// contact from where they first touched
+fn swept_contact(g_collider_1: &GlobalCollider, g_collider_2: &GlobalCollider) -> Option<Contact> {
+    let relative_motion = g_collider_1.motion - g_collider_2.motion;
+    if relative_motion == Vec2::ZERO {
+        return None;
+    }
+
+    let radius_1 = g_collider_1.bounding.bounding_radius();
+    let radius_2 = g_collider_2.bounding.bounding_radius();
+
+    let first_is_moving = radius_1 <= radius_2;
+    let (moving, other, motion) = if first_is_moving {
+        (g_collider_1, g_collider_2, relative_motion)
+    } else {
+        (g_collider_2, g_collider_1, -relative_motion)
+    };
+
+    let end = moving.bounding.aabb().center();
+    let toi = swept::segment_toi(
+        end - motion,
+        end,
+        moving.bounding.bounding_radius(),
+        &other.bounding,
+    )?;
+
+    // move back to where it first touched
+    let at_impact = moving.bounding.translated(-motion * (1. - toi));
+    let contact = contact::contact(&at_impact, &other.bounding);
+
+    Some(match first_is_moving {
+        true => contact,
+        false => contact.flipped(),
+    })
+}
+
 fn has_collided(collider1: ColliderType, collider2: ColliderType) -> bool {
     match (collider1, collider2) {
+        (ColliderType::Compound(parts), other) | (other, ColliderType::Compound(parts)) => parts
+            .into_iter()
+            .any(|part| has_collided(part, other.clone())),
+
         (ColliderType::Rectangle(aabb1), ColliderType::Rectangle(aabb2)) => {
             aabb1.intersects(&aabb2)
         }
@@ -75,44 +217,86 @@ fn has_collided(collider1: ColliderType, collider2: ColliderType) -> bool {
             circle1.intersects(&circle2)
         }
 
-        (ColliderType::Ring(inner1, outer1), ColliderType::Ring(inner2, outer2)) => {
-            //TODO: fix! not work right
-            outer1.intersects(&outer2) && !inner1.intersects(&inner2)
-        }
+        (ColliderType::Ring(inner1, outer1), ColliderType::Ring(inner2, outer2)) => annuli_overlap(
+            outer1.center,
+            inner1.radius(),
+            outer1.radius(),
+            outer2.center,
+            inner2.radius(),
+            outer2.radius(),
+        ),
 
         (ColliderType::Ring(inner, outer), ColliderType::Circle(circle))
         | (ColliderType::Circle(circle), ColliderType::Ring(inner, outer)) => {
-            // outer.intersects(&circle) && !inner.intersects(&circle)
-            if !outer.intersects(&circle) {
-                return false;
-            }
+            // a circle is just a ring without a hole
+            annuli_overlap(
+                outer.center,
+                inner.radius(),
+                outer.radius(),
+                circle.center,
+                0.,
+                circle.radius(),
+            )
+        }
 
-            // if circle fully within ring (not touching it), then not collision
-            let circle_fully_in_inner =
-                inner.center.distance(circle.center) + circle.radius() <= inner.radius();
-            !circle_fully_in_inner
+        // everything left over is some sort of polygon
+        (ColliderType::Ring(inner, outer), other) | (other, ColliderType::Ring(inner, outer)) => {
+            let Some(vertices) = other.polygon() else {
+                return false;
+            };
+            ring_polygon_overlap(&inner, &outer, &vertices)
         }
 
-        (ColliderType::Ring(inner, outer), ColliderType::Rectangle(aabb))
-        | (ColliderType::Rectangle(aabb), ColliderType::Ring(inner, outer)) => {
-            // outer.intersects(&rectangle) && !inner.intersects(&rectangle)
-            if !outer.intersects(&aabb) {
+        (ColliderType::Circle(circle), other) | (other, ColliderType::Circle(circle)) => {
+            let Some(vertices) = other.polygon() else {
                 return false;
-            }
+            };
+            sat::polygon_circle_overlap(&vertices, circle.center, circle.radius())
+        }
 
-            // if rectangle fully within ring (not touching it), then not collision
-            let inner_collider = inner.circle.into();
-            let rect_corners_in_inner = contains_point(&inner_collider, aabb.min)
-                && contains_point(&inner_collider, aabb.max)
-                && contains_point(&inner_collider, Vec2::new(aabb.min.x, aabb.max.y))
-                && contains_point(&inner_collider, Vec2::new(aabb.max.x, aabb.min.y));
-            !rect_corners_in_inner
+        (collider1, collider2) => {
+            let (Some(vertices1), Some(vertices2)) = (collider1.polygon(), collider2.polygon())
+            else {
+                return false;
+            };
+            sat::polygons_overlap(&vertices1, &vertices2)
         }
     }
 }
 
-/// WARN: for now, only BoundingCircle implimented!
-fn contains_point(collider: &ColliderType, point: Vec2) -> bool {
+/// exact overlap of two filled rings (inner radius of 0 makes a circle)
+/// rings overlap if some distance `r` from center 1 lies within ring 1, and a circle of radius
+/// `r` around center 1 passes through ring 2
+fn annuli_overlap(
+    center1: Vec2,
+    inner1: f32,
+    outer1: f32,
+    center2: Vec2,
+    inner2: f32,
+    outer2: f32,
+) -> bool {
+    let distance = center1.distance(center2);
+    // points `r` away from center 1 are between |distance - r| and distance + r from center 2
+    let lowest_r = inner1.max(distance - outer2).max(inner2 - distance);
+    let highest_r = outer1.min(distance + outer2);
+    lowest_r <= highest_r
+}
+
+/// polygon touches the outer circle, but isn't hidden entirely inside the hole
+fn ring_polygon_overlap(inner: &BoundingCircle, outer: &BoundingCircle, vertices: &[Vec2]) -> bool {
+    if !sat::polygon_circle_overlap(vertices, outer.center, outer.radius()) {
+        return false;
+    }
+
+    // polygon is convex, so all vertices in the hole means the whole polygon is in the hole
+    let vertices_in_inner = vertices
+        .iter()
+        .all(|vertex| inner.center.distance(*vertex) < inner.radius());
+    !vertices_in_inner
+}
+
+/// is the point inside (or on the edge of) the collider?
+pub fn contains_point(collider: &ColliderType, point: Vec2) -> bool {
     match collider {
         ColliderType::Circle(circle) => circle.center.distance(point) <= circle.radius(),
         ColliderType::Rectangle(aabb) => {
@@ -122,7 +306,13 @@ fn contains_point(collider: &ColliderType, point: Vec2) -> bool {
                 && point.y >= aabb.min.y
                 && point.y <= aabb.max.y
         }
-        _ => todo!("impliment point detection for collidertype!"),
+        ColliderType::OrientedRectangle(rect) => rect.contains_point(point),
+        ColliderType::Ring(inner, outer) => {
+            let distance = outer.center.distance(point);
+            inner.radius() <= distance && distance <= outer.radius()
+        }
+        ColliderType::Polygon(polygon) => sat::polygon_contains_point(&polygon.vertices, point),
+        ColliderType::Compound(parts) => parts.iter().any(|part| contains_point(part, point)),
     }
 }
 
@@ -176,3 +366,375 @@ pub fn has_collided_with_component<T: Component>(
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CollisionEvent, collider_type::ColliderType, contact::contact, contains_point,
+        convex_poly::ConvexPoly, has_collided, oriented_rect::OrientedRect,
+    };
+    use bevy::prelude::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    /// 2 by 2 square
+    fn rect() -> ColliderType {
+        ColliderType::new_rect(2., 2.)
+    }
+
+    /// 2 by 2 square turned into a diamond, corners ~1.414 from center
+    fn diamond() -> ColliderType {
+        ColliderType::OrientedRectangle(OrientedRect::new(Vec2::ZERO, Vec2::ONE, FRAC_PI_4))
+    }
+
+    fn circle() -> ColliderType {
+        ColliderType::new_circle(1.)
+    }
+
+    /// hole big enough to fit any of the unit shapes
+    fn ring() -> ColliderType {
+        ColliderType::new_annulus(2., 3.)
+    }
+
+    fn triangle() -> ColliderType {
+        ColliderType::Polygon(ConvexPoly::new(vec![
+            Vec2::new(-1., -1.),
+            Vec2::new(1., -1.),
+            Vec2::new(0., 1.),
+        ]))
+    }
+
+    /// circles at x = -3 and x = 3, with an empty gap between them
+    fn compound() -> ColliderType {
+        ColliderType::Compound(vec![
+            circle().translated(Vec2::new(-3., 0.)),
+            circle().translated(Vec2::new(3., 0.)),
+        ])
+    }
+
+    /// checks both orders give the same answer
+    fn overlaps(collider1: &ColliderType, collider2: &ColliderType, offset: Vec2) -> bool {
+        let moved = collider2.translated(offset);
+        let forwards = has_collided(collider1.clone(), moved.clone());
+        let backwards = has_collided(moved, collider1.clone());
+        assert_eq!(forwards, backwards, "overlap depends on order");
+        forwards
+    }
+
+    #[test]
+    fn rect_rect() {
+        assert!(overlaps(&rect(), &rect(), Vec2::new(1.5, 0.)));
+        assert!(!overlaps(&rect(), &rect(), Vec2::new(2.5, 0.)));
+    }
+
+    #[test]
+    fn rect_oriented_rect() {
+        assert!(overlaps(&rect(), &diamond(), Vec2::new(2., 0.)));
+        assert!(!overlaps(&rect(), &diamond(), Vec2::new(2.6, 0.)));
+    }
+
+    #[test]
+    fn rect_circle() {
+        assert!(overlaps(&rect(), &circle(), Vec2::new(1.5, 0.)));
+        // bounding boxes overlap, but circle misses the corner
+        assert!(!overlaps(&rect(), &circle(), Vec2::new(1.8, 1.8)));
+    }
+
+    #[test]
+    fn rect_ring() {
+        assert!(overlaps(&rect(), &ring(), Vec2::new(2.5, 0.)));
+        assert!(!overlaps(&rect(), &ring(), Vec2::new(4.5, 0.)));
+        // inside the hole
+        assert!(!overlaps(&rect(), &ring(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn rect_polygon() {
+        assert!(overlaps(&rect(), &triangle(), Vec2::new(1.5, 0.)));
+        assert!(!overlaps(&rect(), &triangle(), Vec2::new(2.5, 0.)));
+    }
+
+    #[test]
+    fn rect_compound() {
+        assert!(overlaps(&rect(), &compound(), Vec2::new(2.5, 0.)));
+        // in the gap between the parts
+        assert!(!overlaps(&rect(), &compound(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn oriented_rect_oriented_rect() {
+        assert!(overlaps(&diamond(), &diamond(), Vec2::new(2.5, 0.)));
+        assert!(!overlaps(&diamond(), &diamond(), Vec2::new(3., 0.)));
+    }
+
+    #[test]
+    fn oriented_rect_circle() {
+        assert!(overlaps(&diamond(), &circle(), Vec2::new(2.2, 0.)));
+        // bounding boxes overlap, but circle misses the edge
+        assert!(!overlaps(&diamond(), &circle(), Vec2::new(1.6, 1.6)));
+    }
+
+    #[test]
+    fn oriented_rect_ring() {
+        assert!(overlaps(&diamond(), &ring(), Vec2::new(2.5, 0.)));
+        assert!(!overlaps(&diamond(), &ring(), Vec2::new(5., 0.)));
+        assert!(!overlaps(&diamond(), &ring(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn oriented_rect_polygon() {
+        assert!(overlaps(&diamond(), &triangle(), Vec2::new(1.5, 0.)));
+        assert!(!overlaps(&diamond(), &triangle(), Vec2::new(3., 0.)));
+    }
+
+    #[test]
+    fn oriented_rect_compound() {
+        assert!(overlaps(&diamond(), &compound(), Vec2::new(2.5, 0.)));
+        assert!(!overlaps(&diamond(), &compound(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn circle_circle() {
+        assert!(overlaps(&circle(), &circle(), Vec2::new(1.5, 0.)));
+        assert!(!overlaps(&circle(), &circle(), Vec2::new(2.5, 0.)));
+    }
+
+    #[test]
+    fn circle_ring() {
+        assert!(overlaps(&circle(), &ring(), Vec2::new(2.5, 0.)));
+        assert!(!overlaps(&circle(), &ring(), Vec2::new(4.5, 0.)));
+        assert!(!overlaps(&circle(), &ring(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn circle_polygon() {
+        assert!(overlaps(&circle(), &triangle(), Vec2::new(1.5, 0.)));
+        assert!(!overlaps(&circle(), &triangle(), Vec2::new(0., 2.5)));
+    }
+
+    #[test]
+    fn circle_compound() {
+        assert!(overlaps(&circle(), &compound(), Vec2::new(2.5, 0.)));
+        assert!(!overlaps(&circle(), &compound(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn ring_ring() {
+        assert!(overlaps(&ring(), &ring(), Vec2::new(5., 0.)));
+        assert!(!overlaps(&ring(), &ring(), Vec2::new(7., 0.)));
+        // one ring sitting in the hole of a bigger one
+        let big_ring = ColliderType::new_annulus(5., 6.);
+        assert!(!overlaps(&big_ring, &ring(), Vec2::ZERO));
+        assert!(overlaps(&big_ring, &ring(), Vec2::new(2.5, 0.)));
+    }
+
+    #[test]
+    fn tangent_rings_touch() {
+        let ring = ColliderType::new_annulus(1., 2.);
+        assert!(overlaps(&ring, &ring, Vec2::new(4., 0.)));
+        assert!(!overlaps(&ring, &ring, Vec2::new(4.01, 0.)));
+    }
+
+    #[test]
+    fn concentric_rings() {
+        let ring = ColliderType::new_annulus(1., 2.);
+        // one ring sits in the other's hole
+        let outer_ring = ColliderType::new_annulus(3., 4.);
+        assert!(!overlaps(&ring, &outer_ring, Vec2::ZERO));
+        // outer edge exactly on the other's inner edge
+        let touching_ring = ColliderType::new_annulus(2., 3.);
+        assert!(overlaps(&ring, &touching_ring, Vec2::ZERO));
+        let wide_ring = ColliderType::new_annulus(1., 3.);
+        let other_wide_ring = ColliderType::new_annulus(2., 4.);
+        assert!(overlaps(&wide_ring, &other_wide_ring, Vec2::ZERO));
+        assert!(overlaps(&ring, &ring, Vec2::ZERO));
+    }
+
+    #[test]
+    fn circle_touching_ring_edges() {
+        let ring = ColliderType::new_annulus(1., 2.);
+        let small_circle = ColliderType::new_circle(0.5);
+        // from inside the hole
+        assert!(overlaps(&ring, &small_circle, Vec2::new(0.5, 0.)));
+        assert!(!overlaps(&ring, &small_circle, Vec2::new(0.49, 0.)));
+        // from outside the ring
+        assert!(overlaps(&ring, &circle(), Vec2::new(3., 0.)));
+        assert!(!overlaps(&ring, &circle(), Vec2::new(3.01, 0.)));
+        // filling the hole and more
+        assert!(overlaps(&ring, &ColliderType::new_circle(1.5), Vec2::ZERO));
+    }
+
+    #[test]
+    fn point_on_ring_edges() {
+        let ring = ColliderType::new_annulus(1., 2.);
+        assert!(contains_point(&ring, Vec2::new(1., 0.)));
+        assert!(contains_point(&ring, Vec2::new(0., -2.)));
+        assert!(contains_point(&ring, Vec2::new(1.5, 0.)));
+        assert!(!contains_point(&ring, Vec2::new(0.999, 0.)));
+        assert!(!contains_point(&ring, Vec2::new(2.001, 0.)));
+        assert!(!contains_point(&ring, Vec2::ZERO));
+    }
+
+    #[test]
+    fn ring_polygon() {
+        assert!(overlaps(&ring(), &triangle(), Vec2::new(2.5, 0.)));
+        assert!(!overlaps(&ring(), &triangle(), Vec2::new(4.5, 0.)));
+        assert!(!overlaps(&ring(), &triangle(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn ring_compound() {
+        // parts sit on the band
+        assert!(overlaps(&ring(), &compound(), Vec2::ZERO));
+        assert!(!overlaps(&ring(), &compound(), Vec2::new(0., 10.)));
+    }
+
+    #[test]
+    fn polygon_polygon() {
+        assert!(overlaps(&triangle(), &triangle(), Vec2::new(1.5, 0.)));
+        assert!(!overlaps(&triangle(), &triangle(), Vec2::new(2.5, 0.)));
+    }
+
+    #[test]
+    fn polygon_compound() {
+        assert!(overlaps(&triangle(), &compound(), Vec2::new(2.5, 0.)));
+        assert!(!overlaps(&triangle(), &compound(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn compound_compound() {
+        assert!(overlaps(&compound(), &compound(), Vec2::new(0., 1.)));
+        assert!(!overlaps(&compound(), &compound(), Vec2::new(0., 3.)));
+    }
+
+    /// triangle with its point facing down
+    fn upside_down_triangle() -> ColliderType {
+        ColliderType::Polygon(ConvexPoly::new(vec![
+            Vec2::new(-1., 1.),
+            Vec2::new(0., -1.),
+            Vec2::new(1., 1.),
+        ]))
+    }
+
+    fn assert_near(actual: Vec2, expected: Vec2, what: &str) {
+        assert!(
+            actual.abs_diff_eq(expected, 1e-3),
+            "{what}: expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn contact_point_normal_and_depth() {
+        // collider 1 stays at the origin, collider 2 gets moved by the offset
+        let cases = [
+            (
+                "circle circle",
+                circle(),
+                circle(),
+                Vec2::new(1.5, 0.),
+                Vec2::X,
+                0.5,
+                Vec2::new(0.75, 0.),
+            ),
+            (
+                "rect circle",
+                rect(),
+                circle(),
+                Vec2::new(1.5, 0.),
+                Vec2::X,
+                0.5,
+                Vec2::new(1.25, 0.),
+            ),
+            (
+                "circle rect",
+                circle(),
+                rect(),
+                Vec2::new(1.5, 0.),
+                Vec2::X,
+                0.5,
+                Vec2::new(0.25, 0.),
+            ),
+            (
+                "rect oriented rect",
+                rect(),
+                diamond(),
+                Vec2::new(2.2, 0.),
+                Vec2::X,
+                1. - (2.2 - 2f32.sqrt()),
+                Vec2::new((1. + 2.2 - 2f32.sqrt()) / 2., 0.),
+            ),
+            (
+                "rect polygon",
+                rect(),
+                upside_down_triangle(),
+                Vec2::new(0., 1.8),
+                Vec2::Y,
+                0.2,
+                Vec2::new(0., 0.9),
+            ),
+            (
+                // ring pushes the circle back into the hole
+                "ring circle in hole",
+                ring(),
+                circle(),
+                Vec2::new(1.5, 0.),
+                Vec2::NEG_X,
+                0.5,
+                Vec2::new(2.25, 0.),
+            ),
+        ];
+
+        for (name, collider1, collider2, offset, normal, depth, point) in cases {
+            let moved = collider2.translated(offset);
+            assert!(has_collided(collider1.clone(), moved.clone()), "{name}");
+            let contact = contact(&collider1, &moved);
+            assert_near(contact.normal, normal, name);
+            assert!(
+                (contact.depth - depth).abs() < 1e-3,
+                "{name}: expected depth {depth}, got {}",
+                contact.depth
+            );
+            assert_near(contact.point, point, name);
+        }
+    }
+
+    #[test]
+    fn contact_normal_points_from_first_to_second() {
+        let pairs = [
+            (circle(), circle()),
+            (rect(), circle()),
+            (diamond(), rect()),
+            (triangle(), triangle()),
+            (triangle(), diamond()),
+        ];
+        let offsets = [
+            Vec2::new(1.2, 0.3),
+            Vec2::new(-0.4, 1.1),
+            Vec2::new(-1., -0.8),
+        ];
+
+        for (collider1, collider2) in pairs {
+            for offset in offsets {
+                let moved = collider2.translated(offset);
+                let forwards = contact(&collider1, &moved);
+                let backwards = contact(&moved, &collider1);
+                assert!(forwards.normal.dot(offset) > 0., "{collider1:?} {moved:?}");
+                assert_near(backwards.normal, -forwards.normal, "flipped normal");
+                assert!((backwards.depth - forwards.depth).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn collision_event_contact_faces_away_from_entity() {
+        let entity1 = Entity::from_raw(1);
+        let entity2 = Entity::from_raw(2);
+        let event = CollisionEvent(
+            entity1,
+            entity2,
+            contact(&circle(), &circle().translated(Vec2::new(1.5, 0.))),
+        );
+        assert_eq!(event.contact_for(entity1).unwrap().normal, Vec2::X);
+        assert_eq!(event.contact_for(entity2).unwrap().normal, Vec2::NEG_X);
+        assert!(event.contact_for(Entity::from_raw(3)).is_none());
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::{math::bounding::Aabb2d, prelude::*};
+use serde::Deserialize;
+
+/// rectangle that can be rotated, unlike Aabb2d
+#[derive(Reflect, Debug, Deserialize, Clone, Copy)]
+pub struct OrientedRect {
+    pub center: Vec2,
+    pub half_size: Vec2,
+    /// radians, counter clockwise
+    pub angle: f32,
+}
+
+impl OrientedRect {
+    pub fn new(center: Vec2, half_size: Vec2, angle: f32) -> Self {
+        OrientedRect {
+            center,
+            half_size,
+            angle,
+        }
+    }
+
+    pub fn from_aabb(aabb: &Aabb2d) -> Self {
+        OrientedRect::new((aabb.min + aabb.max) / 2., (aabb.max - aabb.min) / 2., 0.)
+    }
+
+    /// rect moved and rotated into world space
+    pub fn convert_to_global(&self, g_transform: &GlobalTransform) -> Self {
+        let g_angle = g_transform.rotation().to_euler(EulerRot::XYZ).2;
+        OrientedRect::new(
+            g_transform.transform_point(self.center.extend(0.)).xy(),
+            self.half_size,
+            self.angle + g_angle,
+        )
+    }
+
+    /// corners, counter clockwise starting bottom left (before rotation)
+    pub fn corners(&self) -> [Vec2; 4] {
+        let rotation = Rot2::radians(self.angle);
+        let Vec2 { x, y } = self.half_size;
+        [
+            Vec2::new(-x, -y),
+            Vec2::new(x, -y),
+            Vec2::new(x, y),
+            Vec2::new(-x, y),
+        ]
+        .map(|corner| self.center + rotation * corner)
+    }
+
+    /// converts a world point into the rect's own (unrotated) space, centered at 0
+    pub fn to_local(self, point: Vec2) -> Vec2 {
+        Rot2::radians(-self.angle) * (point - self.center)
+    }
+
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        let local = self.to_local(point);
+        local.x.abs() <= self.half_size.x && local.y.abs() <= self.half_size.y
+    }
+
+    pub fn aabb(&self) -> Aabb2d {
+        let (sin, cos) = self.angle.sin_cos();
+        let half_extents = Vec2::new(
+            cos.abs() * self.half_size.x + sin.abs() * self.half_size.y,
+            sin.abs() * self.half_size.x + cos.abs() * self.half_size.y,
+        );
+        Aabb2d::new(self.center, half_extents)
+    }
+}
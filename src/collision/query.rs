@@ -0,0 +1,304 @@
+use super::{
+    collider::{Collider, CollisionLayer},
+    collider_type::ColliderType,
+    contact::{self, Contact},
+    contains_point, has_collided, swept,
+};
+use bevy::{
+    ecs::system::SystemParam,
+    math::bounding::{BoundingVolume, IntersectsVolume},
+    prelude::*,
+};
+
+/// most steps a shape cast takes while looking for the first overlap, so very thin shapes
+/// still finish
+const MAX_SHAPE_CAST_STEPS: usize = 1024;
+/// halvings a shape cast does to narrow down the exact hit
+const SHAPE_CAST_REFINE_STEPS: usize = 8;
+
+/// which colliders a query looks at
+#[derive(Clone, Debug, Default)]
+pub struct LayerFilter {
+    /// None means every layer
+    layers: Option<Vec<CollisionLayer>>,
+    /// always skipped (i.e., the ship doing the looking)
+    excluded: Vec<Entity>,
+}
+
+impl LayerFilter {
+    /// look at every collider
+    pub fn all() -> Self {
+        LayerFilter::default()
+    }
+
+    /// only look at colliders on these layers
+    pub fn layers(layers: Vec<CollisionLayer>) -> Self {
+        LayerFilter {
+            layers: Some(layers),
+            excluded: Vec::new(),
+        }
+    }
+
+    /// skip the given entity
+    pub fn excluding(mut self, entity: Entity) -> Self {
+        self.excluded.push(entity);
+        self
+    }
+
+    fn allows(&self, entity: Entity, collider: &Collider) -> bool {
+        !self.excluded.contains(&entity)
+            && self
+                .layers
+                .as_ref()
+                .is_none_or(|layers| layers.contains(&collider.collision_layer))
+    }
+}
+
+/// something a ray or shape cast ran into
+#[derive(Clone, Copy, Debug)]
+pub struct RayHit {
+    pub entity: Entity,
+    /// world position of the hit
+    pub point: Vec2,
+    /// surface normal of the hit collider, pointing back towards the caster
+    pub normal: Vec2,
+    /// how far the cast went before hitting
+    pub distance: f32,
+}
+
+/// line of sight, shape cast, and point questions about every Collider
+#[derive(SystemParam)]
+pub struct CollisionQuery<'w, 's> {
+    colliders: Query<'w, 's, (Entity, &'static Collider, &'static GlobalTransform)>,
+}
+
+impl CollisionQuery<'_, '_> {
+    /// colliders (in world space) that pass the filter
+    fn filtered<'a>(
+        &'a self,
+        filter: &'a LayerFilter,
+    ) -> impl Iterator<Item = (Entity, ColliderType)> + 'a {
+        self.colliders
+            .iter()
+            .filter(|(entity, collider, _)| filter.allows(*entity, collider))
+            .map(|(entity, collider, g_transform)| {
+                (entity, collider.bounding.convert_to_global(g_transform))
+            })
+    }
+
+    /// everything the ray hits, closest first
+    pub fn raycast(
+        &self,
+        origin: Vec2,
+        direction: Dir2,
+        max_distance: f32,
+        filter: &LayerFilter,
+    ) -> Vec<RayHit> {
+        let end = origin + direction * max_distance;
+
+        let mut hits: Vec<RayHit> = self
+            .filtered(filter)
+            .filter_map(|(entity, g_bounding)| {
+                let toi = swept::segment_toi(origin, end, 0., &g_bounding)?;
+                let point = origin + direction * max_distance * toi;
+                Some(RayHit {
+                    entity,
+                    point,
+                    normal: surface_normal(&g_bounding, point, *direction, toi),
+                    distance: max_distance * toi,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// closest thing the ray hits
+    pub fn raycast_first(
+        &self,
+        origin: Vec2,
+        direction: Dir2,
+        max_distance: f32,
+        filter: &LayerFilter,
+    ) -> Option<RayHit> {
+        self.raycast(origin, direction, max_distance, filter)
+            .into_iter()
+            .next()
+    }
+
+    /// true if nothing passing the filter is between the two points
+    pub fn line_of_sight(&self, from: Vec2, to: Vec2, filter: &LayerFilter) -> bool {
+        let Ok(direction) = Dir2::new(to - from) else {
+            return true;
+        };
+        self.raycast_first(from, direction, from.distance(to), filter)
+            .is_none()
+    }
+
+    /// sweeps `shape` (centered on 0, 0) from `origin` along `direction`, and gives everything
+    /// it runs into, closest first
+    pub fn shape_cast(
+        &self,
+        shape: &ColliderType,
+        origin: Vec2,
+        direction: Dir2,
+        max_distance: f32,
+        filter: &LayerFilter,
+    ) -> Vec<RayHit> {
+        let radius = shape.bounding_radius();
+        let shape_offset = shape.aabb().center();
+        let start = origin + shape_offset;
+        let end = start + direction * max_distance;
+
+        let mut hits: Vec<RayHit> = self
+            .filtered(filter)
+            .filter_map(|(entity, g_bounding)| {
+                // bounding circle always touches first and leaves last, so the exact hit is
+                // somewhere while it's touching
+                let circle_toi = swept::segment_toi(start, end, radius, &g_bounding)?;
+                let circle_exit_toi =
+                    1. - swept::segment_toi(end, start, radius, &g_bounding).unwrap_or(0.);
+                let distance = first_overlap(
+                    shape,
+                    origin,
+                    direction,
+                    (circle_toi * max_distance, circle_exit_toi * max_distance),
+                    &g_bounding,
+                )?;
+
+                let moved_shape = shape.translated(origin + direction * distance);
+                let contact = contact::contact(&moved_shape, &g_bounding);
+                Some(RayHit {
+                    entity,
+                    point: contact.point,
+                    normal: -contact.normal,
+                    distance,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// every collider containing the point
+    pub fn point(&self, point: Vec2, filter: &LayerFilter) -> Vec<Entity> {
+        self.filtered(filter)
+            .filter(|(_, g_bounding)| contains_point(g_bounding, point))
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    /// every collider overlapping the given shape (already in world space), with the contact
+    /// normal pointing from the shape to the collider
+    pub fn overlaps(&self, shape: &ColliderType, filter: &LayerFilter) -> Vec<(Entity, Contact)> {
+        let shape_aabb = shape.aabb();
+        self.filtered(filter)
+            .filter(|(_, g_bounding)| shape_aabb.intersects(&g_bounding.aabb()))
+            .filter(|(_, g_bounding)| has_collided(shape.clone(), g_bounding.clone()))
+            .map(|(entity, g_bounding)| (entity, contact::contact(shape, &g_bounding)))
+            .collect()
+    }
+}
+
+/// steps the shape forward over `range` (distances along the cast where its bounding circle
+/// touches `other`) until it really overlaps, then narrows it down
+/// steps are no bigger than either shape, so neither can slip past the other between steps
+/// returns distance along the cast
+fn first_overlap(
+    shape: &ColliderType,
+    origin: Vec2,
+    direction: Dir2,
+    range: (f32, f32),
+    other: &ColliderType,
+) -> Option<f32> {
+    let overlaps_at = |distance: f32| {
+        has_collided(
+            shape.translated(origin + direction * distance),
+            other.clone(),
+        )
+    };
+
+    let (mut clear, last) = range;
+    let step = shape
+        .aabb()
+        .half_size()
+        .min_element()
+        .min(other.aabb().half_size().min_element())
+        .max((last - clear) / MAX_SHAPE_CAST_STEPS as f32)
+        .max(f32::EPSILON);
+
+    if overlaps_at(clear) {
+        return Some(clear);
+    }
+    loop {
+        let next = (clear + step).min(last);
+        if overlaps_at(next) {
+            // hit is somewhere between clear and next
+            let mut hit = next;
+            for _ in 0..SHAPE_CAST_REFINE_STEPS {
+                let middle = (clear + hit) / 2.;
+                if overlaps_at(middle) {
+                    hit = middle;
+                } else {
+                    clear = middle;
+                }
+            }
+            return Some(hit);
+        }
+        if next >= last {
+            return None;
+        }
+        clear = next;
+    }
+}
+
+/// outward normal of the collider where the ray hit it
+fn surface_normal(g_bounding: &ColliderType, point: Vec2, direction: Vec2, toi: f32) -> Vec2 {
+    if toi == 0. {
+        // started inside, so just point back at the caster
+        return -direction;
+    }
+
+    match g_bounding {
+        ColliderType::Circle(circle) => (point - circle.center).normalize_or(-direction),
+        ColliderType::Ring(inner, outer) => {
+            let from_center = point - outer.center;
+            // hit the inner edge from the hole, so normal points inwards
+            if from_center.length() < (inner.radius() + outer.radius()) / 2. {
+                (-from_center).normalize_or(-direction)
+            } else {
+                from_center.normalize_or(-direction)
+            }
+        }
+        ColliderType::Compound(parts) => parts
+            .iter()
+            .min_by(|a, b| {
+                let distance =
+                    |part: &ColliderType| part.aabb().closest_point(point).distance_squared(point);
+                distance(a).total_cmp(&distance(b))
+            })
+            .map_or(-direction, |part| {
+                surface_normal(part, point, direction, toi)
+            }),
+        polygon_like => {
+            let Some(vertices) = polygon_like.polygon() else {
+                return -direction;
+            };
+            // outward normal of edge closest to the hit
+            (0..vertices.len())
+                .map(|i| (vertices[i], vertices[(i + 1) % vertices.len()]))
+                .min_by(|(a1, a2), (b1, b2)| {
+                    let distance = |start: Vec2, end: Vec2| {
+                        super::sat::closest_point_on_segment(start, end, point)
+                            .distance_squared(point)
+                    };
+                    distance(*a1, *a2).total_cmp(&distance(*b1, *b2))
+                })
+                .map_or(-direction, |(start, end)| {
+                    (-(end - start).perp()).normalize_or(-direction)
+                })
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use super::{CollisionEvent, collider::Collider, contact::Contact};
+use crate::{
+    Damage,
+    health::Health,
+    space::mass::{Inertia, Mass},
+    velocity::{AngularVelocity, Velocity},
+};
+use bevy::prelude::*;
+
+/// tweakable settings for how colliders physically react to each other
+#[derive(Resource, Reflect, Debug, Clone)]
+pub struct ResponseSettings {
+    /// how much of the overlap is pushed apart each frame
+    pub correction_percent: f32,
+    /// overlap allowed before pushing apart, stops jittering
+    pub correction_slop: f32,
+    /// impact speeds below this do no damage
+    pub damage_threshold: f32,
+    /// damage per unit of impact speed above the threshold
+    pub damage_per_speed: f32,
+}
+
+impl Default for ResponseSettings {
+    fn default() -> Self {
+        ResponseSettings {
+            correction_percent: 0.8,
+            correction_slop: 0.05,
+            damage_threshold: 10.,
+            damage_per_speed: 2.,
+        }
+    }
+}
+
+/// mass, inertia, and contact offset of one side of the collision
+struct Body {
+    inverse_mass: f32,
+    inverse_inertia: f32,
+    /// contact point relative to body center
+    offset: Vec2,
+}
+
+impl Body {
+    fn new(
+        mass: Option<&Mass>,
+        inertia: Option<&Inertia>,
+        collider: &Collider,
+        offset: Vec2,
+    ) -> Self {
+        // entities without mass act like they weigh 1
+        let mass = mass.map_or(1., |mass| mass.0);
+        let inertia = inertia.map_or_else(
+            || collider.bounding.moment_of_inertia(mass),
+            |inertia| inertia.0,
+        );
+        Body {
+            inverse_mass: inverse(mass),
+            inverse_inertia: inverse(inertia),
+            offset,
+        }
+    }
+
+    /// push body by `impulse` at the contact point
+    fn apply_impulse(
+        &self,
+        impulse: Vec2,
+        velocity: &mut Velocity,
+        angular_velocity: &mut AngularVelocity,
+    ) {
+        velocity.0 += impulse * self.inverse_mass;
+        angular_velocity.0 += self.offset.perp_dot(impulse) * self.inverse_inertia;
+    }
+
+    /// how hard body is to push along `dir` at the contact point
+    fn effective_inverse_mass(&self, dir: Vec2) -> f32 {
+        self.inverse_mass + self.offset.perp_dot(dir).powi(2) * self.inverse_inertia
+    }
+}
+
+fn inverse(val: f32) -> f32 {
+    if val > 0. { 1. / val } else { 0. }
+}
+
+/// velocity of the body's point at `offset` from its center
+fn point_velocity(velocity: &Velocity, angular_velocity: &AngularVelocity, offset: Vec2) -> Vec2 {
+    velocity.0 + offset.perp() * angular_velocity.0
+}
+
+/// bounce colliders with responses off each other, push them apart, and damage them by
+/// how hard they hit
+/// impulses change velocity straight away, so once bounced apart later frames of the same
+/// overlap don't bounce them again
+/// NOTE: moves Transform directly, so only works right for top level entities
+pub fn resolve_collisions(
+    mut events: EventReader<CollisionEvent>,
+    colliders: Query<&Collider>,
+    mut bodies: Query<(
+        &mut Velocity,
+        &mut AngularVelocity,
+        &mut Transform,
+        &GlobalTransform,
+        Option<&Mass>,
+        Option<&Inertia>,
+    )>,
+    health_query: Query<(), With<Health>>,
+    children_query: Query<&Children>,
+    settings: Res<ResponseSettings>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        let (entity1, entity2, contact) = (event.0, event.1, event.2);
+
+        let Ok([collider1, collider2]) = colliders.get_many([entity1, entity2]) else {
+            continue;
+        };
+        let (Some(response1), Some(response2)) = (&collider1.response, &collider2.response) else {
+            // at least one side is only a trigger
+            continue;
+        };
+
+        let Ok(
+            [
+                (
+                    mut velocity1,
+                    mut angular_velocity1,
+                    mut transform1,
+                    g_transform1,
+                    mass1,
+                    inertia1,
+                ),
+                (
+                    mut velocity2,
+                    mut angular_velocity2,
+                    mut transform2,
+                    g_transform2,
+                    mass2,
+                    inertia2,
+                ),
+            ],
+        ) = bodies.get_many_mut([entity1, entity2])
+        else {
+            continue;
+        };
+
+        let body1 = Body::new(
+            mass1,
+            inertia1,
+            collider1,
+            contact.point - g_transform1.translation().xy(),
+        );
+        let body2 = Body::new(
+            mass2,
+            inertia2,
+            collider2,
+            contact.point - g_transform2.translation().xy(),
+        );
+        if body1.inverse_mass + body2.inverse_mass == 0. {
+            continue;
+        }
+
+        let relative_velocity = point_velocity(&velocity2, &angular_velocity2, body2.offset)
+            - point_velocity(&velocity1, &angular_velocity1, body1.offset);
+        let impact_speed = relative_velocity.dot(contact.normal);
+
+        // only bounce if moving towards each other
+        if impact_speed < 0. {
+            let restitution = response1.restitution.min(response2.restitution);
+            let friction = (response1.friction * response2.friction).sqrt();
+
+            let normal_impulse = -(1. + restitution) * impact_speed
+                / (body1.effective_inverse_mass(contact.normal)
+                    + body2.effective_inverse_mass(contact.normal));
+            let mut impulse = contact.normal * normal_impulse;
+
+            // friction slows sliding, but can't be stronger than the hit itself
+            let sliding = relative_velocity - contact.normal * impact_speed;
+            if let Some(tangent) = sliding.try_normalize() {
+                let friction_impulse = -relative_velocity.dot(tangent)
+                    / (body1.effective_inverse_mass(tangent)
+                        + body2.effective_inverse_mass(tangent));
+                let max_friction = friction * normal_impulse;
+                impulse += tangent * friction_impulse.clamp(-max_friction, max_friction);
+            }
+
+            body1.apply_impulse(-impulse, &mut velocity1, &mut angular_velocity1);
+            body2.apply_impulse(impulse, &mut velocity2, &mut angular_velocity2);
+
+            let damage = (-impact_speed - settings.damage_threshold) * settings.damage_per_speed;
+            if damage > 0. {
+                for entity in [entity1, entity2] {
+                    damage_entity(
+                        entity,
+                        damage,
+                        &health_query,
+                        &children_query,
+                        &mut commands,
+                    );
+                }
+            }
+        }
+
+        // push apart so they stop overlapping
+        let correction =
+            correction(&contact, &settings) / (body1.inverse_mass + body2.inverse_mass);
+        transform1.translation -= (correction * body1.inverse_mass).extend(0.);
+        transform2.translation += (correction * body2.inverse_mass).extend(0.);
+    }
+}
+
+fn correction(contact: &Contact, settings: &ResponseSettings) -> Vec2 {
+    contact.normal
+        * (contact.depth - settings.correction_slop).max(0.)
+        * settings.correction_percent
+}
+
+/// damage entity directly if it has health, otherwise split damage between its children
+/// (i.e., ships only have health in their components)
+fn damage_entity(
+    entity: Entity,
+    amount: f32,
+    health_query: &Query<(), With<Health>>,
+    children_query: &Query<&Children>,
+    commands: &mut Commands,
+) {
+    if health_query.contains(entity) {
+        commands.entity(entity).insert(Damage(amount));
+        return;
+    }
+
+    let Ok(children) = children_query.get(entity) else {
+        return;
+    };
+    let damaged: Vec<Entity> = children
+        .into_iter()
+        .copied()
+        .filter(|&child| health_query.contains(child))
+        .collect();
+    for child in &damaged {
+        commands
+            .entity(*child)
+            .insert(Damage(amount / damaged.len() as f32));
+    }
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+// separating axis theorem helpers for convex polygons
+// polygons are given as slices of their vertices in order (either winding)
+
+/// normals of every edge of the polygon (not normalized)
+pub fn axes(vertices: &[Vec2]) -> impl Iterator<Item = Vec2> + '_ {
+    (0..vertices.len()).map(move |i| {
+        let edge = vertices[(i + 1) % vertices.len()] - vertices[i];
+        edge.perp()
+    })
+}
+
+/// (min, max) of the polygon squashed onto the axis
+pub fn project(vertices: &[Vec2], axis: Vec2) -> (f32, f32) {
+    vertices
+        .iter()
+        .map(|vertex| vertex.dot(axis))
+        .fold((f32::MAX, f32::MIN), |(min, max), dist| {
+            (min.min(dist), max.max(dist))
+        })
+}
+
+/// true if no edge of either polygon separates them
+pub fn polygons_overlap(a: &[Vec2], b: &[Vec2]) -> bool {
+    axes(a).chain(axes(b)).all(|axis| {
+        let (a_min, a_max) = project(a, axis);
+        let (b_min, b_max) = project(b, axis);
+        a_min <= b_max && b_min <= a_max
+    })
+}
+
+/// point is inside (or on edge of) the polygon
+pub fn polygon_contains_point(vertices: &[Vec2], point: Vec2) -> bool {
+    let mut has_positive = false;
+    let mut has_negative = false;
+
+    for i in 0..vertices.len() {
+        let edge = vertices[(i + 1) % vertices.len()] - vertices[i];
+        let cross = edge.perp_dot(point - vertices[i]);
+        has_positive |= cross > 0.;
+        has_negative |= cross < 0.;
+    }
+    // inside if point is on the same side of every edge
+    !(has_positive && has_negative)
+}
+
+/// closest point on the polygon's outline to the given point
+pub fn closest_point_on_polygon(vertices: &[Vec2], point: Vec2) -> Vec2 {
+    (0..vertices.len())
+        .map(|i| closest_point_on_segment(vertices[i], vertices[(i + 1) % vertices.len()], point))
+        .min_by(|a, b| {
+            a.distance_squared(point)
+                .total_cmp(&b.distance_squared(point))
+        })
+        .unwrap_or(point)
+}
+
+pub fn closest_point_on_segment(start: Vec2, end: Vec2, point: Vec2) -> Vec2 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared == 0. {
+        return start;
+    }
+    let t = ((point - start).dot(segment) / length_squared).clamp(0., 1.);
+    start + segment * t
+}
+
+pub fn polygon_circle_overlap(vertices: &[Vec2], center: Vec2, radius: f32) -> bool {
+    polygon_contains_point(vertices, center)
+        || closest_point_on_polygon(vertices, center).distance_squared(center) <= radius * radius
+}
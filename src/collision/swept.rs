@@ -0,0 +1,262 @@
+use super::{collider_type::ColliderType, oriented_rect::OrientedRect};
+use bevy::{
+    math::bounding::{Aabb2d, BoundingVolume},
+    prelude::*,
+};
+
+// continuous collision helpers
+// a moving collider is treated as a circle of `radius` travelling from `start` to `end`,
+// so the other collider gets inflated by `radius` and tested against the segment
+
+/// first fraction of the way (0 to 1) from `start` to `end` that touches `other` inflated by
+/// `radius`, or None if the whole motion misses
+pub fn segment_toi(start: Vec2, end: Vec2, radius: f32, other: &ColliderType) -> Option<f32> {
+    match other {
+        ColliderType::Rectangle(aabb) => segment_aabb_toi(start, end, radius, aabb),
+        ColliderType::OrientedRectangle(rect) => {
+            segment_oriented_rect_toi(start, end, radius, rect)
+        }
+        ColliderType::Circle(circle) => {
+            segment_circle_toi(start, end, circle.center, circle.radius() + radius)
+        }
+        ColliderType::Ring(inner, outer) => segment_ring_toi(
+            start,
+            end,
+            outer.center,
+            (inner.radius() - radius).max(0.),
+            outer.radius() + radius,
+        ),
+        ColliderType::Polygon(polygon) => {
+            segment_polygon_toi(start, end, radius, &polygon.vertices)
+        }
+        ColliderType::Compound(parts) => parts
+            .iter()
+            .filter_map(|part| segment_toi(start, end, radius, part))
+            .min_by(f32::total_cmp),
+    }
+}
+
+/// start is inside circle, or first time entering it
+pub fn segment_circle_toi(start: Vec2, end: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+    let motion = end - start;
+    let offset = start - center;
+
+    let c = offset.length_squared() - radius * radius;
+    if c <= 0. {
+        // already inside
+        return Some(0.);
+    }
+
+    let a = motion.length_squared();
+    if a == 0. {
+        return None;
+    }
+    let b = 2. * offset.dot(motion);
+    let discriminant = b * b - 4. * a * c;
+    if discriminant < 0. {
+        return None;
+    }
+
+    let t = (-b - discriminant.sqrt()) / (2. * a);
+    (0. ..=1.).contains(&t).then_some(t)
+}
+
+/// last time the segment is inside the circle (where it leaves), if it leaves at all
+fn segment_circle_exit(start: Vec2, end: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+    let motion = end - start;
+    let offset = start - center;
+
+    let a = motion.length_squared();
+    if a == 0. {
+        return None;
+    }
+    let b = 2. * offset.dot(motion);
+    let c = offset.length_squared() - radius * radius;
+    let discriminant = b * b - 4. * a * c;
+    if discriminant < 0. {
+        return None;
+    }
+
+    let t = (-b + discriminant.sqrt()) / (2. * a);
+    (0. ..=1.).contains(&t).then_some(t)
+}
+
+fn segment_ring_toi(start: Vec2, end: Vec2, center: Vec2, inner: f32, outer: f32) -> Option<f32> {
+    let start_dist = start.distance(center);
+    if start_dist < inner {
+        // start in the hole, so hit when leaving the hole
+        return segment_circle_exit(start, end, center, inner);
+    }
+    // at the outer edge, distance >= inner, so entering outer circle means touching the ring
+    segment_circle_toi(start, end, center, outer)
+}
+
+/// slab test, start is inside box or first time entering it
+fn segment_box_toi(start: Vec2, end: Vec2, min: Vec2, max: Vec2) -> Option<f32> {
+    let motion = end - start;
+    let mut t_enter: f32 = 0.;
+    let mut t_exit: f32 = 1.;
+
+    for axis in 0..2 {
+        if motion[axis].abs() < f32::EPSILON {
+            // moving parallel to slab, so must already be inside it
+            if start[axis] < min[axis] || start[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let t1 = (min[axis] - start[axis]) / motion[axis];
+        let t2 = (max[axis] - start[axis]) / motion[axis];
+        t_enter = t_enter.max(t1.min(t2));
+        t_exit = t_exit.min(t1.max(t2));
+        if t_enter > t_exit {
+            return None;
+        }
+    }
+    Some(t_enter)
+}
+
+/// aabb inflated by radius is two crossed boxes plus a circle on each corner
+fn segment_aabb_toi(start: Vec2, end: Vec2, radius: f32, aabb: &Aabb2d) -> Option<f32> {
+    if radius <= 0. {
+        return segment_box_toi(start, end, aabb.min, aabb.max);
+    }
+
+    let wide = segment_box_toi(
+        start,
+        end,
+        aabb.min - Vec2::new(radius, 0.),
+        aabb.max + Vec2::new(radius, 0.),
+    );
+    let tall = segment_box_toi(
+        start,
+        end,
+        aabb.min - Vec2::new(0., radius),
+        aabb.max + Vec2::new(0., radius),
+    );
+    let corners = [
+        aabb.min,
+        aabb.max,
+        Vec2::new(aabb.min.x, aabb.max.y),
+        Vec2::new(aabb.max.x, aabb.min.y),
+    ]
+    .map(|corner| segment_circle_toi(start, end, corner, radius));
+
+    [wide, tall]
+        .into_iter()
+        .chain(corners)
+        .flatten()
+        .min_by(f32::total_cmp)
+}
+
+/// rotate segment into the rect's space, then it's just an aabb
+fn segment_oriented_rect_toi(
+    start: Vec2,
+    end: Vec2,
+    radius: f32,
+    rect: &OrientedRect,
+) -> Option<f32> {
+    let local_aabb = Aabb2d::new(Vec2::ZERO, rect.half_size);
+    segment_aabb_toi(
+        rect.to_local(start),
+        rect.to_local(end),
+        radius,
+        &local_aabb,
+    )
+}
+
+/// clip segment against each edge of a counter clockwise polygon
+fn segment_convex_toi(start: Vec2, end: Vec2, vertices: &[Vec2]) -> Option<f32> {
+    let motion = end - start;
+    let mut t_enter: f32 = 0.;
+    let mut t_exit: f32 = 1.;
+
+    for i in 0..vertices.len() {
+        let edge = vertices[(i + 1) % vertices.len()] - vertices[i];
+        let outward = -edge.perp();
+
+        // point is inside edge while `distance + t * closing <= 0`
+        let distance = outward.dot(start - vertices[i]);
+        let closing = outward.dot(motion);
+        if closing == 0. {
+            if distance > 0. {
+                // parallel to and outside of edge
+                return None;
+            }
+            continue;
+        }
+
+        let t = -distance / closing;
+        if closing < 0. {
+            t_enter = t_enter.max(t);
+        } else {
+            t_exit = t_exit.min(t);
+        }
+        if t_enter > t_exit {
+            return None;
+        }
+    }
+    Some(t_enter)
+}
+
+/// polygon inflated by radius is the polygon, each edge pushed outwards, and a circle on each
+/// vertex
+fn segment_polygon_toi(start: Vec2, end: Vec2, radius: f32, vertices: &[Vec2]) -> Option<f32> {
+    let mut hits = vec![segment_convex_toi(start, end, vertices)];
+    if radius > 0. {
+        for i in 0..vertices.len() {
+            let (vertex1, vertex2) = (vertices[i], vertices[(i + 1) % vertices.len()]);
+            let outward = -(vertex2 - vertex1).perp().normalize_or_zero() * radius;
+            // counter clockwise like the polygon, so `segment_convex_toi` can clip against it
+            let pushed_edge = [vertex1, vertex1 + outward, vertex2 + outward, vertex2];
+
+            hits.push(segment_convex_toi(start, end, &pushed_edge));
+            hits.push(segment_circle_toi(start, end, vertex1, radius));
+        }
+    }
+    hits.into_iter().flatten().min_by(f32::total_cmp)
+}
+
+/// broadphase box covering the collider over its whole motion
+pub fn swept_aabb(g_bounding: &ColliderType, motion: Vec2) -> Aabb2d {
+    let end = g_bounding.aabb();
+    let start = Aabb2d::new(end.center() - motion, end.half_size());
+    end.merge(&start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::segment_polygon_toi;
+    use bevy::prelude::*;
+
+    /// 2 by 2 square, counter clockwise
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::new(-1., -1.),
+            Vec2::new(1., -1.),
+            Vec2::new(1., 1.),
+            Vec2::new(-1., 1.),
+        ]
+    }
+
+    #[test]
+    fn polygon_edge_strip_hit() {
+        // ends above the top edge, inside its strip but away from the corner circles
+        let toi = segment_polygon_toi(Vec2::new(0., 2.), Vec2::new(0.2, 1.3), 0.5, &square());
+        let toi = toi.expect("should touch the inflated top edge");
+        assert!((toi - 0.5 / 0.7).abs() < 1e-4, "toi was {toi}");
+    }
+
+    #[test]
+    fn polygon_vertex_circle_hit() {
+        // towards the top right corner from outside both strips
+        let toi = segment_polygon_toi(Vec2::new(3., 3.), Vec2::new(1., 1.), 0.5, &square());
+        assert!(toi.is_some());
+    }
+
+    #[test]
+    fn polygon_miss() {
+        let toi = segment_polygon_toi(Vec2::new(0., 3.), Vec2::new(3., 3.), 0.5, &square());
+        assert!(toi.is_none());
+    }
+}
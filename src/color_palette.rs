@@ -15,7 +15,7 @@ pub enum PalColor {
 
 impl PalColor {
     pub fn mix(&self, other: &PalColor, percent: f32) -> PalColor {
-        PalColor::Custom(Color::from(self.clone()).mix(&Color::from(other.clone()), percent))
+        PalColor::Custom(Color::from(*self).mix(&Color::from(*other), percent))
     }
 }
 
@@ -6,6 +6,7 @@ use super::{
 use crate::{
     AppState, debug,
     iterable_enum::IterableEnum,
+    rotation,
     velocity::{AngularVelocity, Velocity},
 };
 use bevy::{platform::collections::HashMap, prelude::*};
@@ -39,7 +40,28 @@ struct BlueprintEntry {
     /// data that is added to parent entity
     modules: Vec<(DataKey, String)>,
     /// each data element is made into a child of the parent
-    children: Vec<(DataKey, String)>,
+    children: Vec<BlueprintChild>,
+}
+
+/// data made into a child, placed relative to the parent
+#[derive(Deserialize, Clone, Debug)]
+struct BlueprintChild {
+    key: DataKey,
+    name: String,
+    /// offset from the parent's center
+    #[serde(default)]
+    position: Vec2,
+    /// direction child faces relative to the parent, in degrees
+    /// (i.e., engines push towards this direction)
+    #[serde(default)]
+    angle: f32,
+}
+
+impl BlueprintChild {
+    fn transform(&self) -> Transform {
+        Transform::from_translation(self.position.extend(0.))
+            .with_rotation(rotation::rad_to_quat(self.angle.to_radians()))
+    }
 }
 
 #[derive(Reflect, Resource, Debug, Default)]
@@ -165,9 +187,7 @@ pub fn entity_from_blueprint(
     let child_entities = child_entities(&mut entry, data_registry, data_assets, commands);
 
     for child_entity in child_entities {
-        // add needed components to children
         commands.entity(entity).add_child(child_entity);
-        blueprint_type.add_components(child_entity, commands);
     }
 
     // add needed components to parent
@@ -208,12 +228,13 @@ fn child_entities(
     let mut child_entities = Vec::new();
 
     for child_module in entry.children.iter_mut() {
-        let mut child_entity = commands.spawn_empty();
+        // children are placed relative to the parent, so only need their own placement
+        let mut child_entity = commands.spawn(child_module.transform());
         {
             data::insert_from_data(
                 &mut child_entity,
-                &child_module.0,
-                &child_module.1,
+                &child_module.key,
+                &child_module.name,
                 data_registry,
                 data_assets,
             );
@@ -0,0 +1,108 @@
+use super::assets_loaded::AssetsLoading;
+use crate::{AppState, collision::collider::CollisionLayer};
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use serde::Deserialize;
+
+pub struct CollisionMatrixPlugin {}
+
+impl Plugin for CollisionMatrixPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<CollisionMatrix>::new(&[".ron"]))
+            .add_systems(OnEnter(AppState::LoadingAssets), load_collision_matrix)
+            .add_systems(OnExit(AppState::LoadingAssets), insert_collision_matrix)
+            .register_type::<CollisionMatrix>();
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CollisionMatrixHandle(Handle<CollisionMatrix>);
+
+/// every collision layer, and which pairs of them can collide
+#[derive(Asset, Clone, Resource, Reflect, Deserialize, Debug, Default)]
+pub struct CollisionMatrix {
+    pub layers: Vec<CollisionLayer>,
+    /// layer pairs that collide with each other, order in pair doesn't matter
+    pub interactions: Vec<(CollisionLayer, CollisionLayer)>,
+}
+
+impl CollisionMatrix {
+    /// do colliders on these layers collide?
+    pub fn layers_collide(&self, layer1: &CollisionLayer, layer2: &CollisionLayer) -> bool {
+        self.interactions
+            .iter()
+            .any(|(a, b)| (a == layer1 && b == layer2) || (a == layer2 && b == layer1))
+    }
+
+    /// every layer the given layer collides with
+    pub fn collides_with(&self, layer: &CollisionLayer) -> Vec<CollisionLayer> {
+        self.layers
+            .iter()
+            .filter(|other| self.layers_collide(layer, other))
+            .cloned()
+            .collect()
+    }
+
+    /// all layer pairs that can collide, each pair listed once
+    pub fn active_pairs(&self) -> Vec<(CollisionLayer, CollisionLayer)> {
+        let mut pairs = Vec::new();
+        for (i, layer1) in self.layers.iter().enumerate() {
+            for layer2 in &self.layers[i..] {
+                if self.layers_collide(layer1, layer2) {
+                    pairs.push((layer1.clone(), layer2.clone()));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// interactions mentioning a layer that isn't in `layers`
+    fn unknown_layers(&self) -> Vec<&CollisionLayer> {
+        self.interactions
+            .iter()
+            .flat_map(|(a, b)| [a, b])
+            .filter(|layer| !self.layers.contains(layer))
+            .collect()
+    }
+}
+
+fn load_collision_matrix(
+    asset_server: Res<AssetServer>,
+    mut assets_loading: ResMut<AssetsLoading>,
+    mut commands: Commands,
+) {
+    let path = "collision_matrix.ron";
+
+    let handle = asset_server.load::<CollisionMatrix>(path);
+    assets_loading.0.push(handle.id().untyped());
+    commands.insert_resource(CollisionMatrixHandle(handle));
+}
+
+fn insert_collision_matrix(
+    handle: Res<CollisionMatrixHandle>,
+    assets: Res<Assets<CollisionMatrix>>,
+    mut commands: Commands,
+) {
+    let Some(matrix) = assets.get(&handle.0) else {
+        error!("collision matrix handle not found! Nothing will collide.");
+        commands.init_resource::<CollisionMatrix>();
+        return;
+    };
+
+    for layer in matrix.unknown_layers() {
+        warn!(
+            "collision layer {:?} is used but never declared!",
+            layer.name()
+        );
+    }
+    for (layer1, layer2) in matrix.active_pairs() {
+        info!(
+            "collision layers active: {} <-> {}",
+            layer1.name(),
+            layer2.name()
+        );
+    }
+
+    commands.insert_resource(matrix.clone());
+    commands.remove_resource::<CollisionMatrixHandle>();
+}
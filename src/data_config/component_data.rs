@@ -4,7 +4,16 @@ use crate::{
     collision::collider::Collider,
     graphic::Graphic,
     particle_system::emitter::ParticleEmitter,
-    ship_composition::{engine::Engine, gun::Gun, sonar::Sonar},
+    ship_composition::{
+        ammo::AmmoStore,
+        countermeasure::{DecoyLauncher, PointDefense},
+        engine::Engine,
+        gun::Gun,
+        sonar::Sonar,
+        turret::Turret,
+    },
+    space::mass::Mass,
+    velocity::damping::Damping,
 };
 use bevy::prelude::*;
 use serde::Deserialize;
@@ -20,6 +29,12 @@ pub enum ComponentData {
     Collider(raw::ColliderRaw),
     Sonar(raw::SonarRaw),
     ParticleEmitter(raw::ParticleEmitterRaw),
+    Mass(Mass),
+    DecoyLauncher(raw::DecoyLauncherRaw),
+    PointDefense(PointDefense),
+    Turret(raw::TurretRaw),
+    AmmoStore(AmmoStore),
+    Damping(Damping),
 }
 
 impl ComponentData {
@@ -34,6 +49,12 @@ impl ComponentData {
             Self::ParticleEmitter(emitter) => {
                 ComponentConcrete::ParticleEmitter(emitter.concrete())
             }
+            Self::Mass(mass) => ComponentConcrete::Mass(*mass),
+            Self::DecoyLauncher(launcher) => ComponentConcrete::DecoyLauncher(launcher.concrete()),
+            Self::PointDefense(defense) => ComponentConcrete::PointDefense(defense.clone()),
+            Self::Turret(turret) => ComponentConcrete::Turret(turret.concrete()),
+            Self::AmmoStore(store) => ComponentConcrete::AmmoStore(store.clone()),
+            Self::Damping(damping) => ComponentConcrete::Damping(*damping),
         }
     }
 }
@@ -46,6 +67,12 @@ pub enum ComponentConcrete {
     Collider(Collider),
     Sonar(Sonar),
     ParticleEmitter(ParticleEmitter),
+    Mass(Mass),
+    DecoyLauncher(DecoyLauncher),
+    PointDefense(PointDefense),
+    Turret(Turret),
+    AmmoStore(AmmoStore),
+    Damping(Damping),
 }
 
 pub fn add_components_to_entity(entity: &mut EntityCommands, components: &[ComponentData]) {
@@ -73,6 +100,24 @@ pub fn add_components_to_entity(entity: &mut EntityCommands, components: &[Compo
             ComponentConcrete::ParticleEmitter(emitter) => {
                 entity.insert(emitter.clone());
             }
+            ComponentConcrete::Mass(mass) => {
+                entity.insert(mass);
+            }
+            ComponentConcrete::DecoyLauncher(launcher) => {
+                entity.insert(launcher.clone());
+            }
+            ComponentConcrete::PointDefense(defense) => {
+                entity.insert(defense.clone());
+            }
+            ComponentConcrete::Turret(turret) => {
+                entity.insert(turret.clone());
+            }
+            ComponentConcrete::AmmoStore(store) => {
+                entity.insert(store.clone());
+            }
+            ComponentConcrete::Damping(damping) => {
+                entity.insert(damping);
+            }
         }
     }
 }
@@ -37,6 +37,8 @@ pub enum DataKey {
     Engine,
     Gun,
     Sonar,
+    Countermeasure,
+    Ammo,
 }
 
 impl DataKey {
@@ -45,15 +47,24 @@ impl DataKey {
             Self::Engine => "engine",
             Self::Gun => "gun",
             Self::Sonar => "sonar",
+            Self::Countermeasure => "countermeasure",
+            Self::Ammo => "ammo",
         }
         .to_owned()
     }
 }
 
 impl IterableEnum for DataKey {
-    type Iter = std::array::IntoIter<DataKey, 3>;
+    type Iter = std::array::IntoIter<DataKey, 5>;
     fn iter() -> Self::Iter {
-        [DataKey::Engine, DataKey::Gun, DataKey::Sonar].into_iter()
+        [
+            DataKey::Engine,
+            DataKey::Gun,
+            DataKey::Sonar,
+            DataKey::Countermeasure,
+            DataKey::Ammo,
+        ]
+        .into_iter()
     }
 }
 
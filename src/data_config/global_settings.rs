@@ -23,6 +23,13 @@ pub struct GlobalSettings {
     pub gravity_const: f32,
     pub velocity_max: f32,
     pub angular_velocity_max: f32,
+    /// how rough gravity from far away groups of bodies can be (0 is exact, ~0.5 is typical)
+    pub gravity_theta: f32,
+    /// keeps gravity from blowing up when bodies get very close
+    pub gravity_softening: f32,
+    /// damping of entities without their own Damping, see [crate::velocity::damping::Damping]
+    pub linear_damping: f32,
+    pub angular_damping: f32,
 }
 
 impl Default for GlobalSettings {
@@ -31,6 +38,10 @@ impl Default for GlobalSettings {
             gravity_const: 10.,
             velocity_max: 200.,
             angular_velocity_max: 20.,
+            gravity_theta: 0.5,
+            gravity_softening: 1.,
+            linear_damping: 0.,
+            angular_damping: 0.,
         }
     }
 }
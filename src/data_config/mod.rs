@@ -1,10 +1,12 @@
 use bevy::prelude::*;
 mod assets_loaded;
 pub mod blueprint;
+pub mod collision_matrix;
 mod component_data;
 pub mod data;
 pub mod global_settings;
 mod raw;
+pub mod star_system;
 
 pub struct TablePlugin {}
 
@@ -12,8 +14,10 @@ impl Plugin for TablePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             global_settings::GlobalSettingsPlugin {},
+            collision_matrix::CollisionMatrixPlugin {},
             data::DataPlugin {},
             blueprint::BlueprintPlugin {},
+            star_system::StarSystemPlugin {},
             assets_loaded::AssetsLoadedPlugin {},
         ));
     }
@@ -1,18 +1,21 @@
 use crate::{
     Health,
     collision::{
-        collider::{Collider, CollisionLayer},
+        collider::{Collider, CollisionLayer, CollisionResponse},
         collider_type::ColliderType,
     },
     particle_system::{emitter::ParticleEmitter, particle::ParticleData},
     primitive::Primitive,
     ship_composition::{
         bullet::BulletData,
+        countermeasure::{DecoyData, DecoyLauncher},
         engine::{Engine, EngineType},
         gun::{Gun, GunData},
         sonar::{Sonar, sonar_pulse::SonarPulseData},
+        turret::Turret,
     },
 };
+use bevy::prelude::*;
 use serde::Deserialize;
 use std::ops::Range;
 
@@ -61,18 +64,90 @@ impl GunRaw {
     }
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct DecoyLauncherRaw {
+    decoy_data: DecoyData,
+    charges: usize,
+    /// seconds between launches
+    cooldown: f32,
+    /// launch by itself when a missile locks on
+    #[serde(default)]
+    automatic: bool,
+}
+
+impl DecoyLauncherRaw {
+    pub fn concrete(&self) -> DecoyLauncher {
+        DecoyLauncher::new(
+            self.decoy_data.clone(),
+            self.charges,
+            self.cooldown,
+            self.automatic,
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TurretRaw {
+    /// total angle turret can sweep, in degrees
+    arc: f32,
+    /// degrees per second
+    traverse_speed: f32,
+}
+
+impl TurretRaw {
+    pub fn concrete(&self) -> Turret {
+        Turret::new(self.arc, self.traverse_speed)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct ColliderRaw {
     bounding: Primitive,
     collision_layer: CollisionLayer,
+    /// overrides which layers the collider hits
+    #[serde(default)]
+    collides_with: Option<Vec<CollisionLayer>>,
+    /// if true, rectangles rotate along with the entity
+    #[serde(default)]
+    oriented: bool,
+    /// if true, swept against others so doesn't tunnel when moving fast
+    #[serde(default)]
+    continuous: bool,
+    /// bounce off other colliders with a response
+    #[serde(default)]
+    response: Option<CollisionResponse>,
+    /// extra shapes and their offsets, making this a compound collider
+    #[serde(default)]
+    compound_parts: Vec<(Primitive, Vec2)>,
 }
 
 impl ColliderRaw {
     pub fn concrete(&self) -> Collider {
-        Collider::new(
-            ColliderType::from(self.bounding.clone()),
-            self.collision_layer.clone(),
-        )
+        let bounding = match (&self.bounding, self.oriented) {
+            (Primitive::Rectangle(x, y), true) => ColliderType::new_oriented_rect(*x, *y),
+            (primitive, _) => ColliderType::from(primitive.clone()),
+        };
+        let bounding = match self.compound_parts.is_empty() {
+            true => bounding,
+            false => ColliderType::Compound(
+                std::iter::once(bounding)
+                    .chain(self.compound_parts.iter().map(|(primitive, offset)| {
+                        ColliderType::from(primitive.clone()).translated(*offset)
+                    }))
+                    .collect(),
+            ),
+        };
+        let mut collider = Collider::new(bounding, self.collision_layer.clone());
+        if let Some(layers) = &self.collides_with {
+            collider = collider.with_collides_with(layers.clone());
+        }
+        if let Some(response) = &self.response {
+            collider = collider.with_response(response.clone());
+        }
+        match self.continuous {
+            true => collider.with_continuous(),
+            false => collider,
+        }
     }
 }
 
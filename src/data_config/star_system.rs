@@ -0,0 +1,71 @@
+use super::assets_loaded::AssetsLoading;
+use crate::{AppState, space::orbit::OrbitalElements, velocity::damping::DragField};
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use serde::Deserialize;
+
+pub struct StarSystemPlugin {}
+
+impl Plugin for StarSystemPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<StarSystem>::new(&[".ron"]))
+            .add_systems(OnEnter(AppState::LoadingAssets), load_star_system)
+            .add_systems(OnExit(AppState::LoadingAssets), insert_star_system);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct StarSystemHandle(Handle<StarSystem>);
+
+/// every planet, moon, and star spawned at the start of the game
+#[derive(Asset, TypePath, Clone, Resource, Deserialize, Debug, Default)]
+pub struct StarSystem {
+    pub bodies: Vec<CelestialBody>,
+}
+
+/// a planet, along with everything orbiting it
+#[derive(Clone, Deserialize, Debug)]
+pub struct CelestialBody {
+    pub name: String,
+    pub mass: f32,
+    /// where body starts, ignored if orbiting
+    #[serde(default)]
+    pub position: Vec2,
+    /// starting velocity, ignored if orbiting
+    #[serde(default)]
+    pub velocity: Vec2,
+    /// orbit around the body this is a satellite of
+    #[serde(default)]
+    pub orbit: Option<OrbitalElements>,
+    /// drag around the body
+    #[serde(default)]
+    pub atmosphere: Option<DragField>,
+    #[serde(default)]
+    pub satellites: Vec<CelestialBody>,
+}
+
+fn load_star_system(
+    asset_server: Res<AssetServer>,
+    mut assets_loading: ResMut<AssetsLoading>,
+    mut commands: Commands,
+) {
+    let path = "star_system.ron";
+
+    let handle = asset_server.load::<StarSystem>(path);
+    assets_loading.0.push(handle.id().untyped());
+    commands.insert_resource(StarSystemHandle(handle));
+}
+
+fn insert_star_system(
+    handle: Res<StarSystemHandle>,
+    assets: Res<Assets<StarSystem>>,
+    mut commands: Commands,
+) {
+    let Some(star_system) = assets.get(&handle.0) else {
+        error!("star system handle not found! Space will be empty.");
+        commands.init_resource::<StarSystem>();
+        return;
+    };
+    commands.insert_resource(star_system.clone());
+    commands.remove_resource::<StarSystemHandle>();
+}
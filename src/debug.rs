@@ -1,20 +1,41 @@
 use crate::{
-    collision::{collider::Collider, collider_type::ColliderType},
+    collision::{
+        broadphase::{BroadphaseSettings, SpatialHash},
+        collider::{Collider, CollisionLayer},
+        collider_type::ColliderType,
+    },
     color_palette::PalColor,
+    lifetime::Lifetime,
+    ship_composition::bullet::{Bullet, BulletAssets, BulletData, BulletType},
+    space::trajectory::TrajectorySettings,
+    velocity::Velocity,
 };
 use bevy::{
-    app::App, gizmos::gizmos::Gizmos, math::bounding::BoundingVolume, prelude::*,
+    app::App,
+    gizmos::gizmos::Gizmos,
+    math::bounding::{BoundingVolume, IntersectsVolume},
+    prelude::*,
     reflect::GetTypeRegistration,
 };
 use bevy_inspector_egui::quick::ResourceInspectorPlugin;
+use std::time::Instant;
 
 pub struct DebugPlugin {}
 
 impl Plugin for DebugPlugin {
     fn build(&self, app: &mut App) {
         // don't care when these run
-        app.add_systems(Startup, draw_grid)
-            .add_systems(Update, (show_rotation, show_colliders, pause_game));
+        app.add_systems(Startup, draw_grid).add_systems(
+            Update,
+            (
+                show_rotation,
+                show_colliders,
+                pause_game,
+                toggle_trajectory,
+                spawn_collision_benchmark.run_if(resource_exists::<BulletAssets>),
+                measure_broadphase,
+            ),
+        );
     }
 }
 
@@ -81,40 +102,64 @@ fn show_rotation(query: Query<&Transform>, mut gizmo: Gizmos) {
 }
 
 fn show_colliders(query: Query<(&GlobalTransform, &Collider, &Visibility)>, mut gizmo: Gizmos) {
-    for (transform, collider, visibility) in &query {
-        let center = transform.translation().xy();
-        let angle_rad = transform.rotation().to_euler(EulerRot::XYZ).2;
-        match collider.bounding {
-            ColliderType::Rectangle(aabb) => {
-                gizmo.rect_2d(
-                    Isometry2d::new(center, Rot2::radians(angle_rad)),
-                    aabb.half_size() * 2.,
-                    PalColor::Green,
-                );
-            }
-            ColliderType::Circle(bounding_circle) => {
-                gizmo.circle_2d(
-                    Isometry2d::from_translation(center),
-                    bounding_circle.radius(),
-                    PalColor::Green,
-                );
-            }
-            ColliderType::Ring(inner, outer) => {
-                gizmo.circle_2d(
-                    Isometry2d::from_translation(center),
-                    inner.radius(),
-                    PalColor::Green,
-                );
-                gizmo.circle_2d(
-                    Isometry2d::from_translation(center),
-                    outer.radius(),
-                    PalColor::Green,
-                );
-            }
-        };
+    for (transform, collider, _) in &query {
+        draw_collider(&collider.bounding.convert_to_global(transform), &mut gizmo);
     }
 }
 
+/// draws collider that is already in world space
+fn draw_collider(bounding: &ColliderType, gizmo: &mut Gizmos) {
+    match bounding {
+        ColliderType::Rectangle(aabb) => {
+            gizmo.rect_2d(
+                Isometry2d::from_translation(aabb.center()),
+                aabb.half_size() * 2.,
+                PalColor::Green,
+            );
+        }
+        ColliderType::OrientedRectangle(rect) => {
+            gizmo.rect_2d(
+                Isometry2d::new(rect.center, Rot2::radians(rect.angle)),
+                rect.half_size * 2.,
+                PalColor::Green,
+            );
+        }
+        ColliderType::Circle(bounding_circle) => {
+            gizmo.circle_2d(
+                Isometry2d::from_translation(bounding_circle.center),
+                bounding_circle.radius(),
+                PalColor::Green,
+            );
+        }
+        ColliderType::Ring(inner, outer) => {
+            gizmo.circle_2d(
+                Isometry2d::from_translation(inner.center),
+                inner.radius(),
+                PalColor::Green,
+            );
+            gizmo.circle_2d(
+                Isometry2d::from_translation(outer.center),
+                outer.radius(),
+                PalColor::Green,
+            );
+        }
+        ColliderType::Polygon(polygon) => {
+            // close the loop back to first vertex
+            let outline = polygon
+                .vertices
+                .iter()
+                .chain(polygon.vertices.first())
+                .copied();
+            gizmo.linestrip_2d(outline, PalColor::Green);
+        }
+        ColliderType::Compound(parts) => {
+            for part in parts {
+                draw_collider(part, gizmo);
+            }
+        }
+    };
+}
+
 fn pause_game(mut time: ResMut<Time<Virtual>>, input: Res<ButtonInput<KeyCode>>) {
     if input.just_pressed(KeyCode::KeyP) {
         if time.is_paused() {
@@ -124,3 +169,107 @@ fn pause_game(mut time: ResMut<Time<Virtual>>, input: Res<ButtonInput<KeyCode>>)
         }
     }
 }
+
+/// press T to show where the player is headed
+fn toggle_trajectory(mut settings: ResMut<TrajectorySettings>, input: Res<ButtonInput<KeyCode>>) {
+    if input.just_pressed(KeyCode::KeyT) {
+        settings.enabled = !settings.enabled;
+    }
+}
+
+/// marks bullets spawned by the collision benchmark
+#[derive(Component)]
+struct BenchmarkBullet {}
+
+/// stress test for the collision broadphase
+/// press B to spawn a cloud of bullets, `measure_broadphase` logs how long finding pairs takes
+fn spawn_collision_benchmark(
+    input: Res<ButtonInput<KeyCode>>,
+    bullet_assets: Res<BulletAssets>,
+    mut commands: Commands,
+) {
+    if !input.just_pressed(KeyCode::KeyB) {
+        return;
+    }
+
+    let bullet_count = 5_000;
+    let field_size = 2_000.;
+    let max_speed = 50.;
+
+    let Some(graphic) = bullet_assets.0.get(&BulletType::Laser) else {
+        return;
+    };
+    let bullet_data = BulletData::new(BulletType::Laser, max_speed, 0.);
+
+    let bullets: Vec<_> = (0..bullet_count)
+        .map(|_| {
+            let position = (Vec2::new(rand::random(), rand::random()) - 0.5) * field_size;
+            let velocity = (Vec2::new(rand::random(), rand::random()) - 0.5) * 2. * max_speed;
+            (
+                // no shooter, so can hit any ship
+                Bullet::new(bullet_data.clone(), &Entity::PLACEHOLDER),
+                BenchmarkBullet {},
+                Transform::from_translation(position.extend(0.)),
+                Velocity(velocity),
+                Collider::new(ColliderType::new_rect(2., 2.), CollisionLayer::BULLET),
+                Lifetime::new(10.),
+                graphic.clone(),
+            )
+        })
+        .collect();
+
+    commands.spawn_batch(bullets);
+    info!("spawned {} benchmark bullets", bullet_count);
+}
+
+/// while benchmark bullets are around, once a second time the broadphase against testing every
+/// pair of AABBs, and log both
+fn measure_broadphase(
+    collider_query: Query<(&Collider, &GlobalTransform)>,
+    benchmark_query: Query<(), With<BenchmarkBullet>>,
+    settings: Res<BroadphaseSettings>,
+    time: Res<Time>,
+    mut since_logged: Local<f32>,
+) {
+    if benchmark_query.is_empty() {
+        return;
+    }
+    *since_logged += time.delta_secs();
+    if *since_logged < 1. {
+        return;
+    }
+    *since_logged = 0.;
+
+    let aabbs: Vec<_> = collider_query
+        .iter()
+        .map(|(collider, g_transform)| collider.bounding.convert_to_global(g_transform).aabb())
+        .collect();
+
+    let start = Instant::now();
+    let mut spatial_hash = SpatialHash::new(settings.cell_size);
+    for (index, aabb) in aabbs.iter().enumerate() {
+        spatial_hash.insert(index, aabb);
+    }
+    let candidate_count = spatial_hash.candidate_pairs().len();
+    let broadphase_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut overlap_count = 0;
+    for (i, aabb1) in aabbs.iter().enumerate() {
+        for aabb2 in &aabbs[i + 1..] {
+            if aabb1.intersects(aabb2) {
+                overlap_count += 1;
+            }
+        }
+    }
+    let brute_force_time = start.elapsed();
+
+    info!(
+        "{} colliders: broadphase {:?} ({} candidate pairs), brute force {:?} ({} overlapping pairs)",
+        aabbs.len(),
+        broadphase_time,
+        candidate_count,
+        brute_force_time,
+        overlap_count
+    );
+}
@@ -7,14 +7,16 @@ use crate::{
     ship,
     ship_composition::{
         bullet::BulletAssets,
+        countermeasure::{DecoyLauncher, PointDefense},
         engine::{Engine, EngineType},
         gun::Gun,
-        sonar::Sonar,
+        sonar::{Sonar, SonarDetectable},
+        turret::{self, Turret},
     },
     velocity::global::GlobalVelocity,
     velocity::{AngularVelocity, Velocity},
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
 
 pub struct PlayerPlugin {}
 
@@ -23,7 +25,15 @@ impl Plugin for PlayerPlugin {
         app.add_systems(OnEnter(AppState::GameReady), setup);
         app.add_systems(
             Update,
-            (player_accelerate, player_rotate, player_shoot, player_sonar)
+            (
+                player_accelerate,
+                player_rotate,
+                player_shoot.after(turret::traverse_turrets),
+                player_sonar,
+                player_decoys,
+                player_aim.before(turret::traverse_turrets),
+                player_reload,
+            )
                 .in_set(SystemUpdateSet::Main),
         );
     }
@@ -86,10 +96,10 @@ fn player_accelerate(
     };
 
     for &child in p_children {
-        if let Ok(mut engine) = engine_query.get_mut(child) {
-            if engine.engine_type == EngineType::Main {
-                throttle_action(&mut engine);
-            }
+        if let Ok(mut engine) = engine_query.get_mut(child)
+            && engine.engine_type == EngineType::Main
+        {
+            throttle_action(&mut engine);
         }
     }
     Ok(())
@@ -107,13 +117,8 @@ fn player_rotate(
 
     let throttle_action = match (a_pressed, d_pressed) {
         (true, true) | (false, false) => |engine: &mut Engine| {
-            // if ship is rotating less than the rotate_threshold, clamp it to 0 so no
-            // annoying drift
-            let rotate_threshold = 0.01;
-            if engine.current_thrust() < rotate_threshold {
-                engine.no_throttle();
-            }
-            engine.hold_throttle();
+            // angular damping stops the spin on its own
+            engine.no_throttle();
         },
         (true, false) => |engine: &mut Engine| {
             engine.full_throttle();
@@ -124,10 +129,10 @@ fn player_rotate(
     };
 
     for &child in p_children {
-        if let Ok(mut engine) = engine_query.get_mut(child) {
-            if engine.engine_type == EngineType::Thruster {
-                throttle_action(&mut engine);
-            }
+        if let Ok(mut engine) = engine_query.get_mut(child)
+            && engine.engine_type == EngineType::Thruster
+        {
+            throttle_action(&mut engine);
         }
     }
     Ok(())
@@ -135,21 +140,21 @@ fn player_rotate(
 
 /// if space bar pressed, have player main gun shoot
 fn player_shoot(
-    player: Query<(Entity, &Children), With<Player>>,
-    guns: Query<(&Gun, &GlobalTransform, &GlobalVelocity)>,
+    player: Query<(Entity, &GlobalTransform, &Children), With<Player>>,
+    mut guns: Query<(&mut Gun, &Transform, &GlobalVelocity), Without<PointDefense>>,
     bullet_assets: Res<BulletAssets>,
     keys: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
 ) -> Result<(), BevyError> {
     if keys.just_pressed(KeyCode::Space) {
-        let (player, p_children) = player.single()?;
+        let (player, p_g_transform, p_children) = player.single()?;
 
         for &child in p_children {
-            if let Ok((gun, g_transform, g_velocity)) = guns.get(child) {
+            if let Ok((mut gun, transform, g_velocity)) = guns.get_mut(child) {
                 gun.try_shoot(
                     &player,
                     &mut commands,
-                    g_transform,
+                    &turret::current_global_transform(p_g_transform, transform),
                     g_velocity,
                     &bullet_assets,
                 );
@@ -177,3 +182,97 @@ fn player_sonar(
     }
     Ok(())
 }
+
+/// if r key pressed, reload every gun that isn't full
+fn player_reload(
+    player: Query<&Children, With<Player>>,
+    mut guns: Query<&mut Gun>,
+    keys: Res<ButtonInput<KeyCode>>,
+) -> Result<(), BevyError> {
+    if keys.just_pressed(KeyCode::KeyR) {
+        let p_children = player.single()?;
+
+        for &child in p_children {
+            if let Ok(mut gun) = guns.get_mut(child) {
+                gun.reload();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// if f key pressed, launch decoys
+fn player_decoys(
+    player: Query<&Children, With<Player>>,
+    mut launchers: Query<(&mut DecoyLauncher, &GlobalTransform, &GlobalVelocity)>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+) -> Result<(), BevyError> {
+    if keys.just_pressed(KeyCode::KeyF) {
+        let p_children = player.single()?;
+
+        for &child in p_children {
+            if let Ok((mut launcher, g_transform, g_velocity)) = launchers.get_mut(child) {
+                launcher.try_launch(&mut commands, g_transform, g_velocity);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// how close to the cursor (in world units) a contact must be for turrets to lead it
+const CURSOR_LOCK_RADIUS: f32 = 30.;
+
+/// point player turrets at the cursor
+/// if sonar has shown something near the cursor, aim ahead of it instead so shots meet it
+fn player_aim(
+    player: Query<&Children, With<Player>>,
+    mut turrets: Query<
+        (&mut Turret, &Gun, &GlobalTransform, &GlobalVelocity),
+        Without<PointDefense>,
+    >,
+    contacts: Query<
+        (&GlobalTransform, &GlobalVelocity, &Visibility),
+        (With<SonarDetectable>, Without<Player>),
+    >,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+) -> Result<(), BevyError> {
+    let p_children = player.single()?;
+    let (camera, c_g_transform) = camera.single()?;
+
+    // cursor off screen, keep aiming where it last was
+    let Some(cursor) = window.single()?.cursor_position() else {
+        return Ok(());
+    };
+    let cursor_point = camera.viewport_to_world_2d(c_g_transform, cursor)?;
+
+    // hidden contacts haven't been picked up by sonar, so the player can't know where they are
+    let contact = contacts
+        .iter()
+        .filter(|(_, _, visibility)| **visibility != Visibility::Hidden)
+        .map(|(c_g_transform, c_g_velocity, _)| (c_g_transform.translation().xy(), c_g_velocity.0))
+        .filter(|(position, _)| position.distance(cursor_point) <= CURSOR_LOCK_RADIUS)
+        .min_by(|(a, _), (b, _)| {
+            a.distance_squared(cursor_point)
+                .total_cmp(&b.distance_squared(cursor_point))
+        });
+
+    for &child in p_children {
+        let Ok((mut turret, gun, g_transform, g_velocity)) = turrets.get_mut(child) else {
+            continue;
+        };
+        turret.aim_point = Some(match contact {
+            Some((c_position, c_velocity)) => turret::lead_target(
+                g_transform.translation().xy(),
+                g_velocity.0,
+                c_position,
+                c_velocity,
+                gun.bullet_data().speed,
+            )
+            .unwrap_or(c_position),
+            None => cursor_point,
+        });
+    }
+    Ok(())
+}
@@ -1,4 +1,9 @@
-use bevy::prelude::*;
+use crate::collision::convex_poly;
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology},
+};
 use serde::Deserialize;
 
 // TODO: integrate with Graphic and Colliders
@@ -8,6 +13,8 @@ pub enum Primitive {
     Rectangle(f32, f32),
     Circle(f32),
     Ring(Annulus),
+    /// vertices of a convex polygon
+    Polygon(#[serde(deserialize_with = "convex_poly::deserialize_vertices")] Vec<Vec2>),
 }
 
 impl Primitive2d for Primitive {}
@@ -36,10 +43,34 @@ impl From<Primitive> for Mesh {
             Primitive::Rectangle(x, y) => Rectangle::from_size(Vec2::new(x, y)).into(),
             Primitive::Circle(radius) => Circle::new(radius).into(),
             Primitive::Ring(ring) => ring.into(),
+            Primitive::Polygon(vertices) => polygon_mesh(vertices),
         }
     }
 }
 
+/// triangle fan over a convex polygon
+fn polygon_mesh(mut vertices: Vec<Vec2>) -> Mesh {
+    if convex_poly::signed_area(&vertices) < 0. {
+        vertices.reverse();
+    }
+
+    let positions: Vec<[f32; 3]> = vertices.iter().map(|v| [v.x, v.y, 0.]).collect();
+    let normals = vec![[0., 0., 1.]; vertices.len()];
+    let uvs = vec![[0., 0.]; vertices.len()];
+    let indices: Vec<u32> = (1..vertices.len().saturating_sub(1) as u32)
+        .flat_map(|i| [0, i, i + 1])
+        .collect();
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
 fn rectangle(x: f32, y: f32) -> Rectangle {
     Rectangle::from_size(Vec2::new(x, y))
 }
@@ -6,6 +6,7 @@ use crate::{
         data::{self, DataKey, DataRegistry, DataTable},
     },
     health::PropagateHealth,
+    space::{gravity::Gravitated, mass::CompositeMass},
 };
 use bevy::prelude::*;
 
@@ -27,7 +28,7 @@ pub fn spawn_ship_from_data(
     commands: &mut Commands,
 ) {
     for part in parts {
-        let child_entity = data::insert_from_data(
+        data::insert_from_data(
             &mut commands.spawn_empty(),
             &part.0,
             &part.1,
@@ -58,9 +59,13 @@ pub fn spawn_ship_from_blueprint(
         commands,
     )?;
 
-    commands
-        .entity(ship)
-        .insert((Ship {}, SonarDetectable::new(), PropagateHealth::new()));
+    commands.entity(ship).insert((
+        Ship {},
+        SonarDetectable::new(),
+        PropagateHealth::new(),
+        CompositeMass::default(),
+        Gravitated {},
+    ));
 
     Some(ship)
 }
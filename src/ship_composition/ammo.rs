@@ -0,0 +1,253 @@
+use super::gun::Gun;
+use crate::SystemUpdateSet;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::time::Duration;
+
+pub struct AmmoPlugin {}
+
+impl Plugin for AmmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, reload_guns.in_set(SystemUpdateSet::Main))
+            .register_type::<AmmoStore>()
+            .register_type::<Magazine>();
+    }
+}
+
+/// ship module holding rounds shared by every gun on the ship that draws from it
+#[derive(Component, Clone, Reflect, Deserialize, Debug)]
+pub struct AmmoStore {
+    pub rounds: usize,
+}
+
+/// what a gun with a magazine is doing
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum ReloadState {
+    /// rounds loaded, can fire
+    Ready,
+    /// refilling the magazine, with how far along it is (0 to 1)
+    Reloading(f32),
+    /// magazine empty, and nothing left to reload it with
+    Empty,
+}
+
+/// rounds loaded into a gun, and where it gets more from
+#[derive(Clone, Reflect, Debug)]
+pub struct Magazine {
+    size: usize,
+    loaded: usize,
+    /// rounds the gun carries itself, None is unlimited
+    reserve: Option<usize>,
+    /// reloads from the ship's AmmoStore instead of its own reserve, if the ship has one
+    shared: bool,
+    reload: Timer,
+    reloading: bool,
+}
+
+impl Magazine {
+    /// starts full
+    pub fn new(size: usize, reload_time: f32, reserve: Option<usize>, shared: bool) -> Self {
+        Magazine {
+            size,
+            loaded: size,
+            reserve,
+            shared,
+            reload: Timer::from_seconds(reload_time, TimerMode::Once),
+            reloading: false,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn loaded(&self) -> usize {
+        self.loaded
+    }
+
+    /// own reserve, not counting any AmmoStore
+    pub fn reserve(&self) -> Option<usize> {
+        self.reserve
+    }
+
+    pub fn shared(&self) -> bool {
+        self.shared
+    }
+
+    pub fn state(&self) -> ReloadState {
+        if self.reloading {
+            ReloadState::Reloading(self.reload.fraction())
+        } else if self.loaded == 0 {
+            ReloadState::Empty
+        } else {
+            ReloadState::Ready
+        }
+    }
+
+    /// use up a round, false if there isn't one ready
+    pub(super) fn take_round(&mut self) -> bool {
+        if self.reloading || self.loaded == 0 {
+            return false;
+        }
+        self.loaded -= 1;
+        true
+    }
+
+    /// start refilling, unless already full or reloading
+    pub fn start_reload(&mut self) {
+        if self.reloading || self.loaded >= self.size {
+            return;
+        }
+        self.reload.reset();
+        self.reloading = true;
+    }
+
+    /// fill up from `opt_store` if given, otherwise from own reserve, once the reload is done
+    /// empty magazines start reloading on their own, if there's anything to reload with
+    fn tick_reload(&mut self, mut opt_store: Option<&mut AmmoStore>, delta: Duration) {
+        let available = match (&opt_store, self.reserve) {
+            (Some(store), _) => Some(store.rounds),
+            (None, reserve) => reserve,
+        };
+
+        if !self.reloading {
+            if self.loaded == 0 && available.is_none_or(|rounds| rounds > 0) {
+                self.start_reload();
+            }
+            return;
+        }
+
+        // nothing to reload with (i.e., asked to reload with an empty reserve)
+        if available == Some(0) {
+            self.reloading = false;
+            return;
+        }
+
+        self.reload.tick(delta);
+        if !self.reload.finished() {
+            return;
+        }
+        self.reloading = false;
+
+        let wanted = self.size - self.loaded;
+        let taken = available.map_or(wanted, |rounds| rounds.min(wanted));
+        self.loaded += taken;
+
+        match (&mut opt_store, &mut self.reserve) {
+            (Some(store), _) => store.rounds -= taken,
+            (None, Some(reserve)) => *reserve -= taken,
+            (None, None) => {}
+        }
+    }
+}
+
+/// tick reloads, filling magazines from their reserve (or the ship's store) when done
+fn reload_guns(
+    gun_query: Query<(&mut Gun, Option<&ChildOf>)>,
+    mut store_query: Query<&mut AmmoStore>,
+    time: Res<Time>,
+) {
+    for (mut gun, opt_child_of) in gun_query {
+        let Some(magazine) = gun.magazine_mut() else {
+            continue;
+        };
+        let opt_store = match magazine.shared {
+            true => opt_child_of.and_then(|child_of| store_query.get_mut(child_of.parent()).ok()),
+            false => None,
+        };
+        magazine.tick_reload(opt_store.map(Mut::into_inner), time.delta());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DONE: Duration = Duration::from_secs(2);
+
+    /// fires every loaded round
+    fn empty(magazine: &mut Magazine) {
+        while magazine.take_round() {}
+    }
+
+    #[test]
+    fn shared_store_drains_across_guns() {
+        let mut store = AmmoStore { rounds: 40 };
+        let mut magazine1 = Magazine::new(30, 1., None, true);
+        let mut magazine2 = Magazine::new(30, 1., None, true);
+        empty(&mut magazine1);
+        for _ in 0..20 {
+            magazine2.take_round();
+        }
+
+        // empty magazine starts reloading on its own, then fills once done
+        magazine1.tick_reload(Some(&mut store), DONE);
+        assert!(matches!(magazine1.state(), ReloadState::Reloading(_)));
+        magazine1.tick_reload(Some(&mut store), DONE);
+        assert_eq!(magazine1.loaded(), 30);
+        assert_eq!(store.rounds, 10);
+
+        // only what's left in the store
+        magazine2.start_reload();
+        magazine2.tick_reload(Some(&mut store), DONE);
+        assert_eq!(magazine2.loaded(), 20);
+        assert_eq!(store.rounds, 0);
+    }
+
+    #[test]
+    fn partial_refill_from_small_reserve() {
+        let mut magazine = Magazine::new(6, 1., Some(4), false);
+        empty(&mut magazine);
+
+        magazine.tick_reload(None, DONE);
+        // can't fire while reloading
+        assert!(!magazine.take_round());
+        magazine.tick_reload(None, DONE);
+
+        assert_eq!(magazine.loaded(), 4);
+        assert_eq!(magazine.reserve(), Some(0));
+        assert_eq!(magazine.state(), ReloadState::Ready);
+    }
+
+    #[test]
+    fn reload_waits_for_reload_time() {
+        let mut magazine = Magazine::new(6, 1., None, false);
+        magazine.take_round();
+        magazine.start_reload();
+
+        magazine.tick_reload(None, Duration::from_millis(500));
+        assert_eq!(magazine.state(), ReloadState::Reloading(0.5));
+        magazine.tick_reload(None, Duration::from_millis(500));
+        assert_eq!(magazine.loaded(), 6);
+    }
+
+    #[test]
+    fn reload_with_empty_reserve_is_cancelled() {
+        let mut magazine = Magazine::new(6, 1., Some(0), false);
+        magazine.take_round();
+        magazine.start_reload();
+        assert!(matches!(magazine.state(), ReloadState::Reloading(_)));
+
+        magazine.tick_reload(None, DONE);
+        assert_eq!(magazine.state(), ReloadState::Ready);
+        assert_eq!(magazine.loaded(), 5);
+    }
+
+    #[test]
+    fn empty_with_nothing_to_reload_with() {
+        let mut magazine = Magazine::new(2, 1., Some(0), false);
+        empty(&mut magazine);
+        assert_eq!(magazine.state(), ReloadState::Empty);
+
+        // doesn't try reloading from nothing
+        magazine.tick_reload(None, DONE);
+        assert_eq!(magazine.state(), ReloadState::Empty);
+        assert!(!magazine.take_round());
+
+        let mut store = AmmoStore { rounds: 0 };
+        let mut shared = Magazine::new(2, 1., None, true);
+        empty(&mut shared);
+        shared.tick_reload(Some(&mut store), DONE);
+        assert_eq!(shared.state(), ReloadState::Empty);
+    }
+}
@@ -1,6 +1,11 @@
 use crate::{
-    AppState, Damage, SystemUpdateSet, collision::CollisionEvent, color_palette::PalColor,
-    graphic::Graphic, health::Health, lifetime::Lifetime, velocity::Velocity,
+    AppState, Damage, SystemUpdateSet,
+    collision::{CollisionEvent, collider::CollisionLayer},
+    color_palette::PalColor,
+    graphic::Graphic,
+    health::Health,
+    lifetime::Lifetime,
+    velocity::Velocity,
 };
 use bevy::prelude::*;
 use serde::Deserialize;
@@ -21,14 +26,19 @@ fn setup(mut commands: Commands) {
     // laser bullet
     bullet_assets.0.insert(
         BulletType::Laser,
-        Graphic::new(Circle::new(2.0).into(), PalColor::Red.into()),
+        Graphic::new(Circle::new(2.0).into(), PalColor::Red),
     );
 
     // missile bullet
     // missiles are bigger
     bullet_assets.0.insert(
         BulletType::Missile,
-        Graphic::new(Circle::new(3.0).into(), PalColor::White.into()),
+        Graphic::new(Circle::new(3.0).into(), PalColor::White),
+    );
+    // flak bullet
+    bullet_assets.0.insert(
+        BulletType::Flak,
+        Graphic::new(Circle::new(1.0).into(), PalColor::White),
     );
     commands.insert_resource(bullet_assets);
 }
@@ -76,6 +86,19 @@ impl Bullet {
 pub enum BulletType {
     Laser,
     Missile,
+    /// point defense rounds, only hit other bullets
+    Flak,
+}
+
+impl BulletType {
+    /// missiles get their own layer, so they can be shot down
+    pub fn collision_layer(&self) -> CollisionLayer {
+        match self {
+            BulletType::Laser => CollisionLayer::BULLET,
+            BulletType::Missile => CollisionLayer::MISSILE,
+            BulletType::Flak => CollisionLayer::FLAK,
+        }
+    }
 }
 
 /// if bullet hit anything not own ship, gets destroyed and adds damage if applicable
@@ -87,7 +110,11 @@ fn bullet_collide(
 ) {
     for collision in collision_events.read() {
         match collision.get_component::<Bullet>(world) {
-            (Some(_), Some(_)) => {
+            (Some(bullet_1), Some(bullet_2)) => {
+                // point defense doesn't shoot down its own side
+                if bullet_1.shooter == bullet_2.shooter {
+                    continue;
+                }
                 commands.entity(collision.0).despawn();
                 commands.entity(collision.1).despawn();
                 info!("bullet collided w/ bullet!");
@@ -0,0 +1,271 @@
+use super::{
+    bullet::{Bullet, BulletAssets, BulletType},
+    gun::Gun,
+    missile::Seeker,
+    sonar::SonarDetectable,
+    turret::{self, Turret},
+};
+use crate::{
+    SystemUpdateSet,
+    collision::{
+        collider::{Collider, CollisionLayer},
+        collider_type::ColliderType,
+    },
+    color_palette::PalColor,
+    graphic::Graphic,
+    lifetime::Lifetime,
+    rotation,
+    space::mass::Mass,
+    velocity::{Velocity, global::GlobalVelocity},
+};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+pub struct CountermeasurePlugin {}
+
+impl Plugin for CountermeasurePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                launcher_cooldown,
+                automatic_decoys.after(launcher_cooldown),
+                point_defense_aim.before(turret::traverse_turrets),
+                point_defense_fire.after(turret::traverse_turrets),
+            )
+                .in_set(SystemUpdateSet::Main),
+        )
+        .register_type::<DecoyLauncher>()
+        .register_type::<Decoy>()
+        .register_type::<PointDefense>();
+    }
+}
+
+/// how launched decoys behave (i.e., bright flares that pull missiles away, or big slow clouds
+/// of chaff that clutter sonar)
+#[derive(Reflect, Deserialize, Clone, Debug)]
+pub struct DecoyData {
+    /// chance per second that a seeker seeing the decoy switches onto it
+    pub lure: f32,
+    /// seconds before decoy burns out
+    pub lifetime: f32,
+    /// speed decoy is thrown out at, on top of the ship's
+    pub ejection_speed: f32,
+    /// size of the decoy, bigger shows up on sonar more
+    pub radius: f32,
+    /// decoys thrown out at once
+    #[serde(default = "default_count")]
+    pub count: usize,
+    /// angle decoys are scattered over, centered on the way the launcher faces, in degrees
+    #[serde(default)]
+    pub spread: f32,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+/// ship module throwing out decoys, which seekers and sonar can mistake for a ship
+#[derive(Component, Clone, Reflect)]
+pub struct DecoyLauncher {
+    decoy_data: DecoyData,
+    /// launches left
+    charges: usize,
+    cooldown: Timer,
+    /// launch by itself whenever a missile locks onto the ship
+    automatic: bool,
+}
+
+impl DecoyLauncher {
+    pub fn new(decoy_data: DecoyData, charges: usize, cooldown: f32, automatic: bool) -> Self {
+        // ready to launch straight away
+        let mut cooldown = Timer::from_seconds(cooldown, TimerMode::Once);
+        let duration = cooldown.duration();
+        cooldown.tick(duration);
+
+        DecoyLauncher {
+            decoy_data,
+            charges,
+            cooldown,
+            automatic,
+        }
+    }
+
+    pub fn charges(&self) -> usize {
+        self.charges
+    }
+
+    /// if off cooldown and has charges left, throw out decoys
+    pub fn try_launch(
+        &mut self,
+        commands: &mut Commands,
+        g_transform: &GlobalTransform,
+        g_velocity: &GlobalVelocity,
+    ) {
+        if self.charges == 0 || !self.cooldown.finished() {
+            return;
+        }
+        self.charges -= 1;
+        self.cooldown.reset();
+
+        let heading = Vec3::from(g_transform.rotation().to_euler(EulerRot::XYZ)).z;
+        let position = g_transform.translation().xy();
+
+        for _ in 0..self.decoy_data.count {
+            let scatter = (rand::random::<f32>() - 0.5) * self.decoy_data.spread;
+            let direction = rotation::rad_to_vec2(heading + scatter.to_radians());
+
+            commands.spawn((
+                Decoy {
+                    lure: self.decoy_data.lure,
+                },
+                Transform::from_translation(position.extend(0.)),
+                Mass(0.1),
+                Velocity(g_velocity.0 + direction * self.decoy_data.ejection_speed),
+                Collider::new(
+                    ColliderType::new_circle(self.decoy_data.radius),
+                    CollisionLayer::DECOY,
+                ),
+                Lifetime::new(self.decoy_data.lifetime),
+                Graphic::new(
+                    Circle::new(self.decoy_data.radius).into(),
+                    PalColor::White,
+                ),
+                // only seen once sonar picks it up, same as ships
+                Visibility::Hidden,
+                SonarDetectable::new(),
+            ));
+        }
+    }
+}
+
+/// false target, shows up on sonar and can pull seekers off their target
+#[derive(Component, Clone, Reflect)]
+#[require(Velocity, Lifetime)]
+pub struct Decoy {
+    /// chance per second that a seeker seeing the decoy switches onto it
+    pub lure: f32,
+}
+
+/// fires the gun on the same entity at enemy bullets and missiles that get too close
+/// aims with the Turret on the same entity
+#[derive(Component, Clone, Reflect, Deserialize, Debug)]
+pub struct PointDefense {
+    /// how close a bullet must get before being shot at
+    radius: f32,
+}
+
+fn launcher_cooldown(query: Query<&mut DecoyLauncher>, time: Res<Time>) {
+    for mut launcher in query {
+        launcher.cooldown.tick(time.delta());
+    }
+}
+
+/// automatic launchers go off when a seeker is locked onto their ship, or any part of it
+fn automatic_decoys(
+    launcher_query: Query<(
+        &mut DecoyLauncher,
+        &GlobalTransform,
+        &GlobalVelocity,
+        &ChildOf,
+    )>,
+    children_query: Query<&Children>,
+    seeker_query: Query<&Seeker>,
+    mut commands: Commands,
+) {
+    let locked: HashSet<Entity> = seeker_query
+        .iter()
+        .filter_map(|seeker| seeker.target)
+        .collect();
+    if locked.is_empty() {
+        return;
+    }
+
+    for (mut launcher, g_transform, g_velocity, child_of) in launcher_query {
+        if !launcher.automatic {
+            continue;
+        }
+
+        let ship = child_of.parent();
+        let ship_locked = locked.contains(&ship)
+            || children_query
+                .get(ship)
+                .is_ok_and(|children| locked.iter().any(|target| children.contains(target)));
+        if ship_locked {
+            launcher.try_launch(&mut commands, g_transform, g_velocity);
+        }
+    }
+}
+
+/// aim point defense turrets ahead of the closest enemy bullet in range
+fn point_defense_aim(
+    defense_query: Query<(
+        &PointDefense,
+        &Gun,
+        &mut Turret,
+        &GlobalTransform,
+        &GlobalVelocity,
+        &ChildOf,
+    )>,
+    bullet_query: Query<(&Bullet, &Transform, &GlobalVelocity)>,
+) {
+    for (defense, gun, mut turret, g_transform, g_velocity, child_of) in defense_query {
+        let ship = child_of.parent();
+        let position = g_transform.translation().xy();
+
+        let Some((b_position, b_velocity)) = bullet_query
+            .iter()
+            .filter(|(bullet, _, _)| {
+                bullet.shooter != ship && bullet.bullet_data.bullet_type != BulletType::Flak
+            })
+            .map(|(_, b_transform, b_g_velocity)| (b_transform.translation.xy(), b_g_velocity.0))
+            .filter(|(b_position, _)| b_position.distance(position) <= defense.radius)
+            .min_by(|(a, _), (b, _)| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+        else {
+            turret.aim_point = None;
+            continue;
+        };
+
+        turret.aim_point = Some(
+            turret::lead_target(
+                position,
+                g_velocity.0,
+                b_position,
+                b_velocity,
+                gun.bullet_data().speed,
+            )
+            .unwrap_or(b_position),
+        );
+    }
+}
+
+/// shoot point defense turrets once they're on the bullet they're aiming at
+fn point_defense_fire(
+    defense_query: Query<
+        (&mut Gun, &Turret, &Transform, &GlobalVelocity, &ChildOf),
+        With<PointDefense>,
+    >,
+    ship_query: Query<&GlobalTransform>,
+    bullet_assets: Res<BulletAssets>,
+    mut commands: Commands,
+) {
+    for (mut gun, turret, transform, g_velocity, child_of) in defense_query {
+        if turret.aim_point.is_none() || !turret.on_target() {
+            continue;
+        }
+        let Ok(s_g_transform) = ship_query.get(child_of.parent()) else {
+            continue;
+        };
+        gun.try_shoot(
+            &child_of.parent(),
+            &mut commands,
+            &turret::current_global_transform(s_g_transform, transform),
+            g_velocity,
+            &bullet_assets,
+        );
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
     SystemUpdateSet,
-    health::Health,
+    health::{Health, Killed},
     particle_system::emitter::ParticleEmitter,
     rotation,
     ship::Ship,
-    velocity::{AngularVelocity, Velocity},
+    space::mass::CenterOfMass,
+    velocity::force::{Force, Torque},
 };
 use bevy::prelude::*;
 use serde::Deserialize;
@@ -15,7 +16,11 @@ impl Plugin for EnginePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (engine_health, engine_thrust, engine_exhaust).in_set(SystemUpdateSet::Main),
+            (engine_health, engine_exhaust).in_set(SystemUpdateSet::Main),
+        )
+        .add_systems(
+            FixedUpdate,
+            (engine_thrust, engine_self_thrust).in_set(SystemUpdateSet::Main),
         )
         .register_type::<Engine>();
     }
@@ -45,6 +50,7 @@ pub struct Engine {
     desired_thrust: f32,
 }
 
+/// what the engine is controlled as, how it moves the ship comes from where it is placed
 #[derive(Reflect, Clone, Debug, Deserialize, PartialEq)]
 pub enum EngineType {
     /// ship go forward
@@ -111,28 +117,39 @@ impl Engine {
 
     /// percent of engine thrust
     pub fn percent_thrust(&self) -> f32 {
-        self.current_thrust / self.max_thrust
+        if self.max_thrust > 0. {
+            self.current_thrust / self.max_thrust
+        } else {
+            0.
+        }
     }
 
-    /// each engine type implements their own way of working
+    /// move current thrust towards desired thrust, limited by max acceleration and health
+    /// damaged engines lose any thrust above what their health still allows
+    fn ramp_thrust(&mut self, health_percent: f32, delta_secs: f32) {
+        let max_change = self.max_acceleration * delta_secs;
+        let change = (self.desired_thrust - self.current_thrust).clamp(-max_change, max_change);
+
+        let max_thrust = self.healthy_max_thrust * health_percent.max(0.);
+        self.current_thrust =
+            (self.current_thrust + change).clamp(-max_thrust * self.reverse_percent, max_thrust);
+    }
+
+    /// push ship from where engine is mounted, in the direction engine faces
+    /// engines off the center of mass also spin the ship (i.e., thrusters at the nose)
     fn thrust(
         &self,
-        transform: &Transform,
-        velocity: &mut Velocity,
-        angular_velocity: &mut AngularVelocity,
-        deltatime: f32,
+        ship_transform: &Transform,
+        engine_transform: &Transform,
+        center_of_mass: Vec2,
+        force: &mut Force,
+        torque: &mut Torque,
     ) {
-        match self.engine_type {
-            // thrusts ship forward
-            EngineType::Main => {
-                velocity.0 +=
-                    rotation::quat_to_vec2(transform.rotation) * self.current_thrust * deltatime;
-            }
-            // makes ship rotate
-            EngineType::Thruster => {
-                angular_velocity.0 += self.current_thrust * deltatime;
-            }
-        }
+        let local_force = rotation::quat_to_vec2(engine_transform.rotation) * self.current_thrust;
+        let lever = engine_transform.translation.xy() - center_of_mass;
+
+        force.0 += (ship_transform.rotation * local_force.extend(0.)).xy();
+        torque.0 += lever.perp_dot(local_force);
     }
 }
 
@@ -153,37 +170,52 @@ impl Default for Engine {
 
 /// calculate and apply actual thrust based on desired thrust
 fn engine_thrust(
-    mut ship_query: Query<(&Transform, &mut Velocity, &mut AngularVelocity, &Children), With<Ship>>,
-    mut engine_query: Query<(&mut Engine, &Health)>,
+    mut ship_query: Query<
+        (
+            &Transform,
+            &mut Force,
+            &mut Torque,
+            &Children,
+            Option<&CenterOfMass>,
+        ),
+        With<Ship>,
+    >,
+    mut engine_query: Query<(&mut Engine, &Health, &Transform), (Without<Ship>, Without<Killed>)>,
     time: Res<Time>,
 ) {
-    for (s_transform, mut s_velocity, mut s_angular_velocity, children) in &mut ship_query {
+    for (s_transform, mut s_force, mut s_torque, children, opt_com) in &mut ship_query {
+        let center_of_mass = opt_com.map_or(Vec2::ZERO, |com| com.0);
         for child in children {
-            if let Ok((mut engine, health)) = engine_query.get_mut(*child) {
-                let thrust_difference = engine.desired_thrust - engine.current_thrust;
-
-                let actual_acceleration = if thrust_difference.abs() > 0. {
-                    thrust_difference.signum() * engine.max_acceleration * time.delta_secs()
-                } else {
-                    0.
-                };
-
-                engine.current_thrust += actual_acceleration.clamp(
-                    -engine.max_thrust * health.percent(),
-                    engine.max_thrust * health.percent(),
-                );
-
+            if let Ok((mut engine, health, e_transform)) = engine_query.get_mut(*child) {
+                engine.ramp_thrust(health.percent(), time.delta_secs());
                 engine.thrust(
                     s_transform,
-                    &mut s_velocity,
-                    &mut s_angular_velocity,
-                    time.delta_secs(),
+                    e_transform,
+                    center_of_mass,
+                    &mut s_force,
+                    &mut s_torque,
                 );
             }
         }
     }
 }
 
+/// engines that are the whole craft (i.e., missiles) push themselves in the direction they face
+fn engine_self_thrust(
+    query: Query<
+        (&mut Engine, &Transform, &mut Force, Option<&Health>),
+        (Without<ChildOf>, Without<Killed>),
+    >,
+    time: Res<Time>,
+) {
+    for (mut engine, transform, mut force, opt_health) in query {
+        let health_percent = opt_health.map_or(1., |health| health.percent());
+        engine.ramp_thrust(health_percent, time.delta_secs());
+
+        force.0 += rotation::quat_to_vec2(transform.rotation) * engine.current_thrust;
+    }
+}
+
 /// alters stats based on engine's health
 fn engine_health(mut query: Query<(&mut Engine, &Health)>) {
     for (mut engine, health) in &mut query {
@@ -198,3 +230,45 @@ fn engine_exhaust(mut query: Query<(&Engine, &mut ParticleEmitter)>) {
         emitter.alter_spawn_rate(engine.percent_thrust());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Engine, EngineType};
+
+    fn running_engine() -> Engine {
+        let mut engine = Engine::new(&EngineType::Main, 10., 100., 0.);
+        engine.full_throttle();
+        engine.ramp_thrust(1., 1.);
+        engine
+    }
+
+    #[test]
+    fn thrust_ramps_by_max_acceleration() {
+        let mut engine = Engine::new(&EngineType::Main, 10., 4., 0.);
+        engine.full_throttle();
+        engine.ramp_thrust(1., 1.);
+        assert_eq!(engine.current_thrust(), 4.);
+        engine.ramp_thrust(1., 1.);
+        engine.ramp_thrust(1., 1.);
+        // doesn't overshoot the throttle
+        assert_eq!(engine.current_thrust(), 10.);
+    }
+
+    #[test]
+    fn damaged_engine_drops_to_its_max_thrust() {
+        let mut engine = running_engine();
+        engine.ramp_thrust(0.5, 1. / 60.);
+        assert_eq!(engine.current_thrust(), 5.);
+    }
+
+    #[test]
+    fn dead_engine_stops_pushing() {
+        let mut engine = running_engine();
+        // engine_health zeroes acceleration, which used to freeze thrust where it was
+        engine.max_acceleration = 0.;
+        engine.max_thrust = 0.;
+        engine.ramp_thrust(0., 1. / 60.);
+        assert_eq!(engine.current_thrust(), 0.);
+        assert_eq!(engine.percent_thrust(), 0.);
+    }
+}
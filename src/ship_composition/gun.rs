@@ -1,10 +1,11 @@
-use super::bullet::{Bullet, BulletAssets, BulletData};
+use super::{
+    ammo::{Magazine, ReloadState},
+    bullet::{Bullet, BulletAssets, BulletData},
+    missile::MissileData,
+};
 use crate::{
     SystemUpdateSet,
-    collision::{
-        collider::{Collider, CollisionLayer},
-        collider_type::ColliderType,
-    },
+    collision::{collider::Collider, collider_type::ColliderType},
     lifetime::Lifetime,
     rotation,
     space::mass::Mass,
@@ -17,8 +18,11 @@ pub struct GunPlugin {}
 
 impl Plugin for GunPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, gun_cooldown.in_set(SystemUpdateSet::Main))
-            .register_type::<Gun>();
+        app.add_systems(
+            Update,
+            (gun_cooldown, gun_burst.after(gun_cooldown)).in_set(SystemUpdateSet::Main),
+        )
+        .register_type::<Gun>();
     }
 }
 
@@ -26,8 +30,42 @@ impl Plugin for GunPlugin {
 #[derive(Reflect, Deserialize, Clone, Debug)]
 pub struct GunData {
     gun_type: GunType,
-    /// rounds per second
+    /// seconds between trigger pulls
     fire_rate: f32,
+    /// bullets fired at once, fanned out across `spread`
+    #[serde(default = "default_one")]
+    pellet_count: usize,
+    /// angle between the outermost pellets, in degrees
+    #[serde(default)]
+    spread: f32,
+    /// each bullet strays randomly up to this many degrees either way
+    #[serde(default)]
+    inaccuracy: f32,
+    /// volleys fired per trigger pull
+    #[serde(default = "default_one")]
+    burst_count: usize,
+    /// seconds between volleys in a burst
+    #[serde(default)]
+    burst_interval: f32,
+    /// how fired missiles fly, only used by HomingMissile
+    #[serde(default)]
+    missile: MissileData,
+    /// volleys per magazine, None never needs reloading
+    #[serde(default)]
+    magazine_size: Option<usize>,
+    /// seconds to refill the magazine
+    #[serde(default)]
+    reload_time: f32,
+    /// rounds carried on top of the magazine, None is unlimited
+    #[serde(default)]
+    reserve_ammo: Option<usize>,
+    /// reload from the ship's AmmoStore instead of own reserve
+    #[serde(default)]
+    shared_ammo: bool,
+}
+
+fn default_one() -> usize {
+    1
 }
 
 impl GunData {
@@ -35,45 +73,134 @@ impl GunData {
         GunData {
             gun_type,
             fire_rate,
+            pellet_count: 1,
+            spread: 0.,
+            inaccuracy: 0.,
+            burst_count: 1,
+            burst_interval: 0.,
+            missile: MissileData::default(),
+            magazine_size: None,
+            reload_time: 0.,
+            reserve_ammo: None,
+            shared_ammo: false,
         }
     }
 }
 
+/// volleys still to be fired from the last trigger pull
+#[derive(Clone, Reflect)]
+struct Burst {
+    shooter: Entity,
+    remaining: usize,
+    timer: Timer,
+}
+
 #[derive(Component, Clone, Reflect)]
 pub struct Gun {
     gun_data: GunData,
     bullet_data: BulletData,
     cooldown: Timer,
+    burst: Option<Burst>,
+    magazine: Option<Magazine>,
 }
 
 impl Gun {
     pub fn new(gun_data: GunData, bullet_data: BulletData) -> Self {
+        // ready to shoot straight away
+        let mut cooldown = Timer::from_seconds(gun_data.fire_rate, TimerMode::Once);
+        let duration = cooldown.duration();
+        cooldown.tick(duration);
+
+        let magazine = gun_data.magazine_size.map(|size| {
+            Magazine::new(
+                size,
+                gun_data.reload_time,
+                gun_data.reserve_ammo,
+                gun_data.shared_ammo,
+            )
+        });
+
         Gun {
-            gun_data: gun_data.clone(),
+            gun_data,
             bullet_data,
-            cooldown: Timer::from_seconds(gun_data.fire_rate, TimerMode::Once),
+            cooldown,
+            burst: None,
+            magazine,
         }
     }
 
+    pub fn bullet_data(&self) -> &BulletData {
+        &self.bullet_data
+    }
+
+    /// None if the gun has unlimited ammo
+    pub fn magazine(&self) -> Option<&Magazine> {
+        self.magazine.as_ref()
+    }
+
+    pub(super) fn magazine_mut(&mut self) -> Option<&mut Magazine> {
+        self.magazine.as_mut()
+    }
+
+    /// guns without a magazine are always Ready
+    pub fn reload_state(&self) -> ReloadState {
+        self.magazine
+            .as_ref()
+            .map_or(ReloadState::Ready, |magazine| magazine.state())
+    }
+
+    /// start refilling the magazine early, does nothing without one
+    pub fn reload(&mut self) {
+        if let Some(magazine) = &mut self.magazine {
+            magazine.start_reload();
+        }
+    }
+
+    /// each volley uses a round from the magazine, guns without one never run out
+    fn take_round(&mut self) -> bool {
+        self.magazine
+            .as_mut()
+            .is_none_or(|magazine| magazine.take_round())
+    }
+
     /// if can shoot, shoot the gun
+    /// guns with a burst keep shooting the rest of it on their own
     pub fn try_shoot(
-        &self,
+        &mut self,
         shooter: &Entity,
         commands: &mut Commands,
         g_transform: &GlobalTransform,
         g_velocity: &GlobalVelocity,
         bullet_assets: &Res<BulletAssets>,
     ) {
-        if self.can_shoot() {
-            self.shoot_bullet(shooter, commands, g_transform, g_velocity, bullet_assets);
+        if !self.can_shoot() || !self.take_round() {
+            return;
+        }
+        self.cooldown.reset();
+        self.shoot_bullet(shooter, commands, g_transform, g_velocity, bullet_assets);
+
+        if self.gun_data.burst_interval <= 0. {
+            // no gap between volleys, whole burst leaves at once
+            for _ in 1..self.gun_data.burst_count {
+                if !self.take_round() {
+                    break;
+                }
+                self.shoot_bullet(shooter, commands, g_transform, g_velocity, bullet_assets);
+            }
+        } else if self.gun_data.burst_count > 1 {
+            self.burst = Some(Burst {
+                shooter: *shooter,
+                remaining: self.gun_data.burst_count - 1,
+                timer: Timer::from_seconds(self.gun_data.burst_interval, TimerMode::Repeating),
+            });
         }
     }
 
     fn can_shoot(&self) -> bool {
-        self.cooldown.finished()
+        self.cooldown.finished() && self.burst.is_none()
     }
 
-    /// spawn bullet depending on gun type (i.e., pulselaser will spawn multiple at diff angles)
+    /// fire one volley, every pellet fanned across the spread and strayed by inaccuracy
     fn shoot_bullet(
         &self,
         shooter: &Entity,
@@ -82,46 +209,80 @@ impl Gun {
         g_velocity: &GlobalVelocity,
         bullet_assets: &Res<BulletAssets>,
     ) {
-        match self.gun_data.gun_type {
-            GunType::Laser => {
-                self.spawn_bullet(shooter, g_transform, g_velocity, commands, bullet_assets);
-            }
-            GunType::PulseLaser => {
-                self.spawn_bullet(shooter, g_transform, g_velocity, commands, bullet_assets);
-            }
-            GunType::HomingMissile => {
-                self.spawn_bullet(shooter, g_transform, g_velocity, commands, bullet_assets);
+        let heading = Vec3::from(g_transform.rotation().to_euler(EulerRot::XYZ)).z;
+        let pellet_count = self.gun_data.pellet_count.max(1);
+
+        for pellet in 0..pellet_count {
+            let fan = if pellet_count > 1 {
+                self.gun_data.spread * (pellet as f32 / (pellet_count - 1) as f32 - 0.5)
+            } else {
+                0.
+            };
+            let stray = (rand::random::<f32>() - 0.5) * 2. * self.gun_data.inaccuracy;
+            let angle = heading + (fan + stray).to_radians();
+
+            match self.gun_data.gun_type {
+                GunType::Laser | GunType::PulseLaser => {
+                    self.spawn_bullet(
+                        shooter,
+                        g_transform,
+                        g_velocity,
+                        angle,
+                        commands,
+                        bullet_assets,
+                    );
+                }
+                GunType::HomingMissile => {
+                    let bullet = self.spawn_bullet(
+                        shooter,
+                        g_transform,
+                        g_velocity,
+                        angle,
+                        commands,
+                        bullet_assets,
+                    );
+                    commands
+                        .entity(bullet)
+                        .insert(self.gun_data.missile.launch());
+                }
             }
         }
     }
 
+    /// spawn bullet heading in the given angle (radians), on top of gun's velocity
     fn spawn_bullet(
         &self,
         shooter: &Entity,
         g_transform: &GlobalTransform,
         g_velocity: &GlobalVelocity,
+        angle: f32,
         commands: &mut Commands,
         bullet_assets: &Res<BulletAssets>,
-    ) {
+    ) -> Entity {
         let graphic = bullet_assets.0.get(&self.bullet_data.bullet_type).unwrap();
 
         let g_position = g_transform.translation().xy();
 
         // velocity without g_velocity
-        let rel_velocity =
-            rotation::rad_to_vec2(Vec3::from(g_transform.rotation().to_euler(EulerRot::XYZ)).z)
-                * self.bullet_data.speed;
+        let rel_velocity = rotation::rad_to_vec2(angle) * self.bullet_data.speed;
         let velocity = rel_velocity + g_velocity.0;
 
-        commands.spawn((
-            Bullet::new(self.bullet_data.clone(), shooter),
-            Transform::from_translation(Vec3::new(g_position.x, g_position.y, 0.)),
-            Mass(1.),
-            Velocity(velocity),
-            Collider::new(ColliderType::new_rect(2., 2.), CollisionLayer::Bullet),
-            Lifetime::new(5.),
-            graphic.clone(),
-        ));
+        commands
+            .spawn((
+                Bullet::new(self.bullet_data.clone(), shooter),
+                Transform::from_translation(Vec3::new(g_position.x, g_position.y, 0.))
+                    .with_rotation(rotation::rad_to_quat(angle)),
+                Mass(1.),
+                Velocity(velocity),
+                Collider::new(
+                    ColliderType::new_rect(2., 2.),
+                    self.bullet_data.bullet_type.collision_layer(),
+                )
+                .with_continuous(),
+                Lifetime::new(5.),
+                graphic.clone(),
+            ))
+            .id()
     }
 }
 
@@ -131,7 +292,7 @@ pub enum GunType {
     Laser,
     /// short bursts, less accuracy (think shotgun)
     PulseLaser,
-    /// lock onto enemy ships (implement countermeasures?)
+    /// lock onto enemy ships the shooter's sonar has found (implement countermeasures?)
     HomingMissile,
 }
 
@@ -140,3 +301,42 @@ fn gun_cooldown(query: Query<&mut Gun>, time: Res<Time>) {
         gun.cooldown.tick(time.delta());
     }
 }
+
+/// fire the rest of any bursts that are due
+fn gun_burst(
+    query: Query<(&mut Gun, &GlobalTransform, &GlobalVelocity)>,
+    bullet_assets: Res<BulletAssets>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (mut gun, g_transform, g_velocity) in query {
+        let Some(mut burst) = gun.burst.take() else {
+            continue;
+        };
+        burst.timer.tick(time.delta());
+
+        let volleys = (burst.timer.times_finished_this_tick() as usize).min(burst.remaining);
+        for _ in 0..volleys {
+            // magazine ran dry part way through, burst ends early
+            if !gun.take_round() {
+                burst.remaining = 0;
+                break;
+            }
+            gun.shoot_bullet(
+                &burst.shooter,
+                &mut commands,
+                g_transform,
+                g_velocity,
+                &bullet_assets,
+            );
+            burst.remaining -= 1;
+        }
+
+        if burst.remaining > 0 {
+            gun.burst = Some(burst);
+        } else {
+            // cooldown starts once the whole burst is out
+            gun.cooldown.reset();
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use super::{
+    bullet::Bullet,
+    countermeasure::Decoy,
+    engine::{Engine, EngineType},
+    sonar::Sonar,
+};
+use crate::{
+    SystemUpdateSet, rotation,
+    velocity::{Velocity, global::GlobalVelocity},
+};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+pub struct MissilePlugin {}
+
+impl Plugin for MissilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (burn_fuel, (lock_on, guide_missiles).chain()).in_set(SystemUpdateSet::Main),
+        )
+        .register_type::<Missile>()
+        .register_type::<Seeker>();
+    }
+}
+
+/// how missiles fired from a gun fly, set in `gun.ron`
+#[derive(Reflect, Deserialize, Clone, Debug)]
+pub struct MissileData {
+    /// engine's max thrust
+    pub thrust: f32,
+    /// seconds engine can burn for, missile coasts afterwards
+    pub fuel: f32,
+    /// fastest the missile can turn, in degrees per second
+    pub turn_rate: f32,
+    /// how hard guidance leads the target, usually 3 to 5
+    #[serde(default = "default_navigation_constant")]
+    pub navigation_constant: f32,
+    /// widest angle either side of the nose the seeker can see, in degrees
+    #[serde(default = "default_seeker_angle")]
+    pub seeker_angle: f32,
+}
+
+fn default_navigation_constant() -> f32 {
+    4.
+}
+
+fn default_seeker_angle() -> f32 {
+    45.
+}
+
+impl Default for MissileData {
+    fn default() -> Self {
+        MissileData {
+            thrust: 100.,
+            fuel: 3.,
+            turn_rate: 90.,
+            navigation_constant: default_navigation_constant(),
+            seeker_angle: default_seeker_angle(),
+        }
+    }
+}
+
+impl MissileData {
+    /// components turning a bullet into a missile
+    pub fn launch(&self) -> (Missile, Seeker, Engine) {
+        let mut engine = Engine::new(&EngineType::Main, self.thrust, self.thrust * 10., 0.);
+        engine.full_throttle();
+
+        (
+            Missile {
+                fuel: Timer::from_seconds(self.fuel, TimerMode::Once),
+                turn_rate: self.turn_rate.to_radians(),
+                navigation_constant: self.navigation_constant,
+            },
+            Seeker::new(self.seeker_angle.to_radians()),
+            engine,
+        )
+    }
+}
+
+/// self propelled bullet, steered by its Seeker until fuel runs out
+#[derive(Component, Clone, Reflect)]
+#[require(Velocity, Seeker)]
+pub struct Missile {
+    fuel: Timer,
+    /// radians per second
+    turn_rate: f32,
+    navigation_constant: f32,
+}
+
+impl Missile {
+    pub fn has_fuel(&self) -> bool {
+        !self.fuel.finished()
+    }
+
+    /// radians to rotate velocity by this step to home in on the target, counter clockwise is
+    /// positive
+    fn turn(
+        &self,
+        line_of_sight: Vec2,
+        relative_velocity: Vec2,
+        velocity: Vec2,
+        delta_secs: f32,
+    ) -> f32 {
+        let closing_speed = -line_of_sight.dot(relative_velocity) / line_of_sight.length();
+        let desired_turn_rate = if closing_speed > 0. {
+            let line_of_sight_rate =
+                line_of_sight.perp_dot(relative_velocity) / line_of_sight.length_squared();
+            // lateral acceleration, turned into how fast velocity needs to rotate
+            self.navigation_constant * closing_speed * line_of_sight_rate / velocity.length()
+        } else {
+            // falling behind, where PN doesn't work, so just turn towards the target
+            velocity.angle_to(line_of_sight).signum() * self.turn_rate
+        };
+
+        let max_turn = self.turn_rate * delta_secs;
+        (desired_turn_rate * delta_secs).clamp(-max_turn, max_turn)
+    }
+}
+
+/// what the missile is locked onto
+/// locks come from the shooter's sonar contacts, and are lost once the target leaves view
+#[derive(Component, Clone, Default, Reflect)]
+pub struct Seeker {
+    pub target: Option<Entity>,
+    /// radians either side of the nose
+    field_of_view: f32,
+}
+
+impl Seeker {
+    pub fn new(field_of_view: f32) -> Self {
+        Seeker {
+            target: None,
+            field_of_view,
+        }
+    }
+
+    /// if position is within view of a missile at `from` facing `heading`
+    fn can_see(&self, from: Vec2, heading: Vec2, position: Vec2) -> bool {
+        let to_position = position - from;
+        to_position != Vec2::ZERO && heading.angle_to(to_position).abs() <= self.field_of_view
+    }
+}
+
+/// engine burns until fuel runs out
+fn burn_fuel(query: Query<(&mut Missile, &mut Engine)>, time: Res<Time>) {
+    for (mut missile, mut engine) in query {
+        missile.fuel.tick(time.delta());
+        if !missile.has_fuel() {
+            engine.no_throttle();
+        }
+    }
+}
+
+/// drop targets the seeker can't see anymore, and pick up new ones from the shooter's sonar
+/// decoys in view can pull the seeker off whatever it's locked onto
+fn lock_on(
+    missile_query: Query<(&mut Seeker, &Transform, &Bullet)>,
+    target_query: Query<&GlobalTransform>,
+    decoy_query: Query<(Entity, &GlobalTransform, &Decoy)>,
+    children_query: Query<&Children>,
+    sonar_query: Query<&Sonar>,
+    time: Res<Time>,
+) {
+    for (mut seeker, transform, bullet) in missile_query {
+        let position = transform.translation.xy();
+        let heading = rotation::quat_to_vec2(transform.rotation);
+
+        let target_in_view = seeker.target.is_some_and(|target| {
+            target_query.get(target).is_ok_and(|t_g_transform| {
+                seeker.can_see(position, heading, t_g_transform.translation().xy())
+            })
+        });
+        if !target_in_view {
+            seeker.target = sonar_contact(
+                &seeker,
+                position,
+                heading,
+                bullet.shooter,
+                &target_query,
+                &children_query,
+                &sonar_query,
+            );
+        }
+
+        for (decoy_entity, d_g_transform, decoy) in decoy_query {
+            if seeker.target == Some(decoy_entity)
+                || !seeker.can_see(position, heading, d_g_transform.translation().xy())
+            {
+                continue;
+            }
+            if rand::random::<f32>() < decoy.lure * time.delta_secs() {
+                seeker.target = Some(decoy_entity);
+                break;
+            }
+        }
+    }
+}
+
+/// closest contact on the shooter's sonar the seeker can see, never the shooter itself
+fn sonar_contact(
+    seeker: &Seeker,
+    position: Vec2,
+    heading: Vec2,
+    shooter: Entity,
+    target_query: &Query<&GlobalTransform>,
+    children_query: &Query<&Children>,
+    sonar_query: &Query<&Sonar>,
+) -> Option<Entity> {
+    let shooter_children = children_query.get(shooter).ok()?;
+    let is_shooter = |entity: Entity| entity == shooter || shooter_children.contains(&entity);
+
+    let mut contacts = Vec::new();
+    for &child in shooter_children {
+        if let Ok(sonar) = sonar_query.get(child) {
+            contacts.extend(sonar.detected_entities());
+        }
+    }
+
+    contacts
+        .into_iter()
+        .filter(|(entity, snapshot)| {
+            !is_shooter(**entity)
+                && target_query.contains(**entity)
+                && seeker.can_see(position, heading, snapshot.position())
+        })
+        .min_by(|(_, a), (_, b)| {
+            position
+                .distance_squared(a.position())
+                .total_cmp(&position.distance_squared(b.position()))
+        })
+        .map(|(entity, _)| *entity)
+}
+
+/// proportional navigation: turn in proportion to how fast the line of sight to the target is
+/// rotating, which leads the target instead of chasing it
+fn guide_missiles(
+    missile_query: Query<(&Missile, &Seeker, &mut Velocity, &mut Transform)>,
+    target_query: Query<(&GlobalTransform, Option<&GlobalVelocity>)>,
+    time: Res<Time>,
+) {
+    for (missile, seeker, mut velocity, mut transform) in missile_query {
+        // out of fuel, nothing left to turn with
+        if !missile.has_fuel() || velocity.0 == Vec2::ZERO {
+            continue;
+        }
+
+        let Some(Ok((t_g_transform, t_opt_g_velocity))) =
+            seeker.target.map(|target| target_query.get(target))
+        else {
+            continue;
+        };
+
+        let line_of_sight = t_g_transform.translation().xy() - transform.translation.xy();
+        if line_of_sight == Vec2::ZERO {
+            continue;
+        }
+        let relative_velocity =
+            t_opt_g_velocity.map_or(Vec2::ZERO, |g_velocity| g_velocity.0) - velocity.0;
+
+        let turn = missile.turn(
+            line_of_sight,
+            relative_velocity,
+            velocity.0,
+            time.delta_secs(),
+        );
+        velocity.0 = Vec2::from_angle(turn).rotate(velocity.0);
+
+        // nose follows velocity, so engine keeps pushing along the path
+        transform.rotation = rotation::rad_to_quat(velocity.0.to_angle());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Missile, MissileData};
+    use bevy::prelude::*;
+
+    const DELTA_SECS: f32 = 0.1;
+
+    fn missile() -> Missile {
+        MissileData::default().launch().0
+    }
+
+    /// missile at the origin flying along x, target ahead at (100, 0)
+    fn turn_towards(target_velocity: Vec2) -> f32 {
+        let velocity = Vec2::new(100., 0.);
+        missile().turn(
+            Vec2::new(100., 0.),
+            target_velocity - velocity,
+            velocity,
+            DELTA_SECS,
+        )
+    }
+
+    #[test]
+    fn turns_the_way_the_target_crosses() {
+        assert!(turn_towards(Vec2::new(0., 10.)) > 0.);
+        assert!(turn_towards(Vec2::new(0., -10.)) < 0.);
+        assert_eq!(turn_towards(Vec2::ZERO), 0.);
+    }
+
+    #[test]
+    fn turn_is_clamped_to_turn_rate() {
+        let max_turn = missile().turn_rate * DELTA_SECS;
+        assert_eq!(turn_towards(Vec2::new(0., 1000.)), max_turn);
+        assert_eq!(turn_towards(Vec2::new(0., -1000.)), -max_turn);
+        // small crossing speeds stay under the limit
+        assert!(turn_towards(Vec2::new(0., 10.)) < max_turn);
+    }
+
+    #[test]
+    fn turns_at_full_rate_towards_target_behind() {
+        let velocity = Vec2::new(100., 0.);
+        let line_of_sight = Vec2::new(-100., 10.);
+        let turn = missile().turn(line_of_sight, -velocity, velocity, DELTA_SECS);
+        assert_eq!(turn, missile().turn_rate * DELTA_SECS);
+    }
+}
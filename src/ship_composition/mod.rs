@@ -1,18 +1,26 @@
 use bevy::prelude::*;
+pub mod ammo;
 pub mod bullet;
+pub mod countermeasure;
 pub mod engine;
 pub mod gun;
+pub mod missile;
 pub mod sonar;
+pub mod turret;
 
 pub struct ShipCompositionPlugin {}
 
 impl Plugin for ShipCompositionPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
+            ammo::AmmoPlugin {},
             bullet::BulletPlugin {},
+            countermeasure::CountermeasurePlugin {},
             engine::EnginePlugin {},
             gun::GunPlugin {},
+            missile::MissilePlugin {},
             sonar::SonarPlugin {},
+            turret::TurretPlugin {},
         ));
     }
 }
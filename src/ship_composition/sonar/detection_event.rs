@@ -1,6 +1,6 @@
 use crate::{SystemUpdateSet, velocity::global::GlobalVelocity};
 
-use super::{SnapshotData, Sonar, SonarDetectable, SonarPulse, sonar_pulse::SonarPulseData};
+use super::{SnapshotData, Sonar, SonarDetectable, SonarPulse};
 use bevy::prelude::*;
 
 pub struct DetectionEventPlugin {}
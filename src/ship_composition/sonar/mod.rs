@@ -1,7 +1,4 @@
-use crate::{
-    SystemUpdateSet, serialization::transform::SerializeableTransform,
-    velocity::global::GlobalVelocity,
-};
+use crate::{serialization::transform::SerializeableTransform, velocity::global::GlobalVelocity};
 use bevy::prelude::*;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -47,6 +44,11 @@ impl Sonar {
     pub fn pulse(&self, entity: Entity, commands: &mut Commands) {
         commands.spawn(SonarPulse::new(entity, self.pulse_data.clone()));
     }
+
+    /// everything this sonar has picked up, as it was when last detected
+    pub fn detected_entities(&self) -> &HashMap<Entity, SnapshotData> {
+        &self.detected_entities
+    }
 }
 
 /// a snapshot of what the entity had at moment of detection
@@ -56,16 +58,24 @@ pub struct SnapshotData {
     g_velocity: GlobalVelocity,
 }
 
+impl SnapshotData {
+    pub fn position(&self) -> Vec2 {
+        self.g_transform.translation.xy()
+    }
+
+    pub fn velocity(&self) -> Vec2 {
+        self.g_velocity.0
+    }
+}
+
 /// detectable by sonar
 #[derive(Component, Reflect, Default)]
-pub struct SonarDetectable {
-    detected: bool,
-}
+pub struct SonarDetectable {}
 
 impl SonarDetectable {
     /// changes visiblity to false and adds component to all children
     pub fn new() -> Self {
-        SonarDetectable { detected: false }
+        SonarDetectable {}
     }
 }
 
@@ -1,10 +1,10 @@
 use super::{Sonar, SonarDetectable, detection_event::DetectionEvent};
 use crate::{
     SystemUpdateSet,
-    collision::{self, CollisionEvent},
     collision::{
         collider::{Collider, CollisionLayer},
         collider_type::ColliderType,
+        lifecycle::{self, CollidingPairs, CollisionEnded, CollisionOngoing, CollisionStarted},
     },
     color_palette::PalColor,
     graphic::Graphic,
@@ -20,7 +20,8 @@ impl Plugin for SonarPulsePlugin {
             Update,
             (
                 spread_sonar_pulse,
-                /*sonar_pulse_hit,*/ sonar_pulse_collide,
+                // sees this frame's pairs
+                sonar_pulse_collide.after(lifecycle::track_collision_pairs),
             )
                 .in_set(SystemUpdateSet::Main),
         )
@@ -82,8 +83,8 @@ fn sonar_pulse_constructor(
     };
 
     let ring = Annulus::new(0., pulse.data.thickness);
-    let graphic = Graphic::new(ring.into(), PalColor::White.into());
-    let collider = Collider::new(ColliderType::from(ring), CollisionLayer::SonarPulse);
+    let graphic = Graphic::new(ring.into(), PalColor::White);
+    let collider = Collider::new(ColliderType::from(ring), CollisionLayer::SONAR_PULSE);
 
     commands.entity(trigger.target()).insert((
         graphic,
@@ -115,84 +116,74 @@ fn spread_sonar_pulse(
         graphic.replace_shape(expanded_ring.into());
         commands.entity(entity).try_insert(Collider::new(
             ColliderType::from(expanded_ring),
-            CollisionLayer::SonarPulse,
+            CollisionLayer::SONAR_PULSE,
         ));
     }
 }
 
+/// tell the sonar when detectable entities enter, stay in, and leave a pulse
 fn sonar_pulse_collide(
-    mut collision_events: EventReader<CollisionEvent>,
-    detectable_query: Query<(Entity, &mut SonarDetectable), With<Collider>>,
-    pulse_query: Query<&SonarPulse, With<Collider>>,
+    mut started_events: EventReader<CollisionStarted>,
+    mut ongoing_events: EventReader<CollisionOngoing>,
+    mut ended_events: EventReader<CollisionEnded>,
+    colliding_pairs: Res<CollidingPairs>,
+    detectable_query: Query<(), With<SonarDetectable>>,
+    pulse_query: Query<&SonarPulse>,
     mut event_writer: EventWriter<DetectionEvent>,
 ) {
-    for (d_entity, mut d_detectable) in detectable_query {
-        let pulse_opt = collision::collided_with_component::<SonarPulse, With<Collider>>(
-            d_entity,
-            &mut collision_events,
-            &pulse_query,
-        );
+    for event in started_events.read() {
+        let Some((pulse, d_entity)) =
+            pulse_and_detectable(event.0, event.1, &pulse_query, &detectable_query)
+        else {
+            continue;
+        };
+        info!("triggered first detected!");
+        event_writer.write(DetectionEvent::FirstDetected(pulse.clone(), d_entity));
+        event_writer.write(DetectionEvent::Detected(pulse.clone(), d_entity));
+    }
 
-        match (d_detectable.detected, pulse_opt) {
-            // (previously detected, detected now)
-            (true, Some(pulse)) => {
-                info!("triggered detected!");
-                event_writer.write(DetectionEvent::Detected(pulse.clone(), d_entity));
-            }
-            (false, Some(pulse)) => {
-                info!("triggered first detected!");
-                event_writer.write(DetectionEvent::FirstDetected(pulse.clone(), d_entity));
-                event_writer.write(DetectionEvent::Detected(pulse.clone(), d_entity));
-            }
-            (true, None) => {
-                info!("triggered last detected!");
-                event_writer.write(DetectionEvent::LastDetected(d_entity));
-            }
-            (false, None) => {}
+    for event in ongoing_events.read() {
+        let Some((pulse, d_entity)) =
+            pulse_and_detectable(event.0, event.1, &pulse_query, &detectable_query)
+        else {
+            continue;
         };
-        d_detectable.detected = pulse_opt.is_some();
+        info!("triggered detected!");
+        event_writer.write(DetectionEvent::Detected(pulse.clone(), d_entity));
     }
-}
 
-/// launch DetectionEvent if entity is detected (in pulse)
-fn sonar_pulse_hit(
-    query: Query<(&SonarPulse, &GlobalTransform)>,
-    mut detectable_query: Query<(Entity, &mut SonarDetectable, &GlobalTransform)>,
-    mut commands: Commands,
-) {
-    for (pulse, pulse_g_transform) in query {
-        for (d_entity, mut d_detectable, d_g_transform) in &mut detectable_query {
-            let distance_away = d_g_transform
-                .translation()
-                .distance(pulse_g_transform.translation());
-
-            let is_detected =
-                pulse.inner_radius() < distance_away && distance_away < pulse.outer_radius();
-
-            if is_detected {
-                commands.trigger(DetectionEvent::Detected(pulse.clone(), d_entity));
-            }
-            match (d_detectable.detected, is_detected) {
-                // (previously detected, detected now)
-                (true, true) => {
-                    info!("triggered detected!");
-                    commands.trigger(DetectionEvent::Detected(pulse.clone(), d_entity));
-                }
-                (true, false) => {
-                    info!("triggered first detected!");
-                    commands.trigger(DetectionEvent::FirstDetected(pulse.clone(), d_entity));
-                    commands.trigger(DetectionEvent::Detected(pulse.clone(), d_entity));
-                }
-                (false, true) => {
-                    info!("triggered last detected!");
-                    commands.trigger(DetectionEvent::Detected(pulse.clone(), d_entity));
-                    //commands.trigger(DetectionEvent::LastDetected(pulse.clone(), d_entity));
-                }
-                (false, false) => {
-                    info!("not before detected and not now!");
-                }
-            };
-            d_detectable.detected = is_detected;
+    for event in ended_events.read() {
+        // pulse may have already despawned, so only the detectable side can be checked
+        let Some(d_entity) = [event.0, event.1]
+            .into_iter()
+            .find(|entity| detectable_query.contains(*entity))
+        else {
+            continue;
+        };
+
+        let in_other_pulse = colliding_pairs
+            .touching(d_entity)
+            .any(|other| pulse_query.contains(other));
+        if !in_other_pulse {
+            info!("triggered last detected!");
+            event_writer.write(DetectionEvent::LastDetected(d_entity));
         }
     }
 }
+
+/// if one entity is a pulse and the other is detectable, give (pulse, detectable)
+fn pulse_and_detectable<'a>(
+    entity1: Entity,
+    entity2: Entity,
+    pulse_query: &'a Query<&SonarPulse>,
+    detectable_query: &Query<(), With<SonarDetectable>>,
+) -> Option<(&'a SonarPulse, Entity)> {
+    [(entity1, entity2), (entity2, entity1)]
+        .into_iter()
+        .find_map(|(pulse_entity, d_entity)| {
+            let pulse = pulse_query.get(pulse_entity).ok()?;
+            detectable_query
+                .contains(d_entity)
+                .then_some((pulse, d_entity))
+        })
+}
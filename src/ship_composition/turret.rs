@@ -0,0 +1,300 @@
+use crate::{SystemUpdateSet, rotation};
+use bevy::prelude::*;
+use std::f32::consts::{PI, TAU};
+
+pub struct TurretPlugin {}
+
+impl Plugin for TurretPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, traverse_turrets.in_set(SystemUpdateSet::Main))
+            .add_observer(turret_constructor)
+            .register_type::<Turret>();
+    }
+}
+
+/// how close (in radians) the turret must face its aim point to count as on target
+const ON_TARGET_TOLERANCE: f32 = 0.05;
+
+/// rotates the gun on the same entity towards `aim_point`, within an arc around where it was
+/// mounted on the ship
+#[derive(Component, Clone, Reflect)]
+pub struct Turret {
+    /// total angle the turret can sweep, centered on where it was mounted, in degrees
+    arc: f32,
+    /// degrees per second
+    traverse_speed: f32,
+    /// world position to point at, None holds still
+    pub aim_point: Option<Vec2>,
+    /// angle turret was mounted at relative to the ship, taken from its Transform when added
+    mount_angle: f32,
+    on_target: bool,
+}
+
+impl Turret {
+    pub fn new(arc: f32, traverse_speed: f32) -> Self {
+        Turret {
+            arc,
+            traverse_speed,
+            aim_point: None,
+            mount_angle: 0.,
+            on_target: false,
+        }
+    }
+
+    /// if facing the aim point, so shots fired now go where they're meant to
+    pub fn on_target(&self) -> bool {
+        self.on_target
+    }
+
+    fn full_circle(&self) -> bool {
+        self.arc >= 360.
+    }
+}
+
+/// remember the angle turret was placed at, which the arc is centered on
+fn turret_constructor(
+    trigger: Trigger<OnAdd, Turret>,
+    mut query: Query<(&mut Turret, Option<&Transform>)>,
+) {
+    let Ok((mut turret, opt_transform)) = query.get_mut(trigger.target()) else {
+        return;
+    };
+    turret.mount_angle = opt_transform.map_or(0., |transform| {
+        rotation::quat_to_vec2(transform.rotation).to_angle()
+    });
+}
+
+/// where to aim so a bullet fired now at `bullet_speed` meets the target, if neither speeds up
+/// bullets carry the shooter's velocity, so this is solved relative to the shooter
+/// None if the bullet can never catch the target
+pub fn lead_target(
+    shooter_position: Vec2,
+    shooter_velocity: Vec2,
+    target_position: Vec2,
+    target_velocity: Vec2,
+    bullet_speed: f32,
+) -> Option<Vec2> {
+    let offset = target_position - shooter_position;
+    let relative_velocity = target_velocity - shooter_velocity;
+
+    // |offset + relative_velocity * t| = bullet_speed * t, solved for the soonest t
+    let a = relative_velocity.length_squared() - bullet_speed.powi(2);
+    let b = 2. * offset.dot(relative_velocity);
+    let c = offset.length_squared();
+
+    let time = if a.abs() < f32::EPSILON {
+        // target as fast as the bullet, only catches it if target is coming closer
+        (b < 0.).then(|| -c / b)?
+    } else {
+        let discriminant = b * b - 4. * a * c;
+        if discriminant < 0. {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        [(-b - root) / (2. * a), (-b + root) / (2. * a)]
+            .into_iter()
+            .filter(|time| *time > 0.)
+            .min_by(|t1, t2| t1.total_cmp(t2))?
+    };
+
+    Some(target_position + relative_velocity * time)
+}
+
+/// where a gun mounted on `parent` is this frame
+/// GlobalTransform only catches up in PostUpdate, so guns fired right after `traverse_turrets`
+/// would otherwise shoot where the turret faced last frame
+pub fn current_global_transform(
+    parent: &GlobalTransform,
+    transform: &Transform,
+) -> GlobalTransform {
+    parent.mul_transform(*transform)
+}
+
+/// angle between -PI and PI
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// turn turrets towards their aim point, no faster than their traverse speed and never out of
+/// their arc
+/// systems setting `aim_point` should run before this, and ones checking `on_target` after
+pub fn traverse_turrets(
+    turret_query: Query<(&mut Turret, &mut Transform, &GlobalTransform, &ChildOf)>,
+    ship_query: Query<&GlobalTransform>,
+    time: Res<Time>,
+) {
+    for (mut turret, mut transform, g_transform, child_of) in turret_query {
+        let Some(aim_point) = turret.aim_point else {
+            turret.on_target = false;
+            continue;
+        };
+
+        let ship_angle = ship_query
+            .get(child_of.parent())
+            .map_or(0., |s_g_transform| {
+                rotation::quat_to_vec2(s_g_transform.rotation()).to_angle()
+            });
+        let aim_angle = (aim_point - g_transform.translation().xy()).to_angle();
+
+        // everything measured from where the turret was mounted
+        let half_arc = (turret.arc / 2.).to_radians();
+        let desired = wrap_angle(aim_angle - ship_angle - turret.mount_angle);
+        let desired = if turret.full_circle() {
+            desired
+        } else {
+            desired.clamp(-half_arc, half_arc)
+        };
+        let current =
+            wrap_angle(rotation::quat_to_vec2(transform.rotation).to_angle() - turret.mount_angle);
+
+        // full circle turrets take the short way round, the rest can't cross behind their arc
+        let difference = if turret.full_circle() {
+            wrap_angle(desired - current)
+        } else {
+            desired - current
+        };
+        let max_step = turret.traverse_speed.to_radians() * time.delta_secs();
+        let facing = current + difference.clamp(-max_step, max_step);
+
+        transform.rotation = rotation::rad_to_quat(turret.mount_angle + facing);
+        turret.on_target = wrap_angle(aim_angle - ship_angle - turret.mount_angle - facing).abs()
+            <= ON_TARGET_TOLERANCE;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::{
+        f32::consts::{FRAC_PI_2, FRAC_PI_4},
+        time::Duration,
+    };
+
+    fn assert_lead(actual: Option<Vec2>, expected: Vec2) {
+        let actual = actual.expect("target should be catchable");
+        assert!(actual.abs_diff_eq(expected, 1e-3), "{actual}");
+    }
+
+    #[test]
+    fn lead_stationary_target() {
+        let target = Vec2::new(100., 0.);
+        assert_lead(
+            lead_target(Vec2::ZERO, Vec2::ZERO, target, Vec2::ZERO, 10.),
+            target,
+        );
+        // moving together is the same as both standing still
+        let velocity = Vec2::new(0., 10.);
+        assert_lead(
+            lead_target(Vec2::ZERO, velocity, target, velocity, 10.),
+            target,
+        );
+    }
+
+    #[test]
+    fn lead_crossing_target() {
+        let target_velocity = Vec2::new(0., 10.);
+        let aim_point = lead_target(
+            Vec2::ZERO,
+            Vec2::ZERO,
+            Vec2::new(100., 0.),
+            target_velocity,
+            20.,
+        )
+        .unwrap();
+
+        // target and bullet get to the aim point at the same time
+        let time = aim_point.y / target_velocity.y;
+        assert!(time > 0.);
+        assert!((aim_point.length() - 20. * time).abs() < 1e-3);
+        assert_eq!(aim_point.x, 100.);
+    }
+
+    #[test]
+    fn uncatchable_target() {
+        let target = Vec2::new(100., 0.);
+        assert!(lead_target(Vec2::ZERO, Vec2::ZERO, target, Vec2::new(30., 0.), 20.).is_none());
+    }
+
+    #[test]
+    fn target_as_fast_as_bullet() {
+        let target = Vec2::new(100., 0.);
+        // coming closer, meets the bullet halfway
+        assert_lead(
+            lead_target(Vec2::ZERO, Vec2::ZERO, target, Vec2::new(-10., 0.), 10.),
+            Vec2::new(50., 0.),
+        );
+        // running away, never caught
+        assert!(lead_target(Vec2::ZERO, Vec2::ZERO, target, Vec2::new(10., 0.), 10.).is_none());
+    }
+
+    /// ship at the origin facing along x, with a turret on it aiming at `aim_point`
+    fn traverse(aim_point: Vec2, delta_secs: f32) -> (f32, bool) {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(delta_secs));
+        world.insert_resource(time);
+
+        let ship = world.spawn(GlobalTransform::IDENTITY).id();
+        let mut turret = Turret::new(90., 180.);
+        turret.aim_point = Some(aim_point);
+        let turret = world
+            .spawn((
+                turret,
+                Transform::default(),
+                GlobalTransform::IDENTITY,
+                ChildOf(ship),
+            ))
+            .id();
+
+        world
+            .run_system_once(traverse_turrets)
+            .expect("system should run");
+
+        let facing = rotation::quat_to_vec2(world.get::<Transform>(turret).unwrap().rotation);
+        let on_target = world.get::<Turret>(turret).unwrap().on_target();
+        (facing.to_angle(), on_target)
+    }
+
+    #[test]
+    fn turret_stops_at_edge_of_arc() {
+        // target almost straight behind, out of the 90 degree arc
+        let (angle, on_target) = traverse(Vec2::new(-10., 1.), 10.);
+        assert!((angle - FRAC_PI_4).abs() < 1e-4, "{angle}");
+        assert!(!on_target);
+
+        let (angle, _) = traverse(Vec2::new(-10., -1.), 10.);
+        assert!((angle + FRAC_PI_4).abs() < 1e-4, "{angle}");
+    }
+
+    #[test]
+    fn turret_turns_at_traverse_speed() {
+        let aim_point = Vec2::new(10., 5.);
+        let (angle, on_target) = traverse(aim_point, 0.1);
+        assert!((angle - 18f32.to_radians()).abs() < 1e-4, "{angle}");
+        assert!(!on_target);
+
+        let (angle, on_target) = traverse(aim_point, 10.);
+        assert!((angle - aim_point.to_angle()).abs() < 1e-4, "{angle}");
+        assert!(on_target);
+    }
+
+    #[test]
+    fn current_global_transform_includes_turret_rotation() {
+        let ship = GlobalTransform::from(
+            Transform::from_xyz(5., 0., 0.).with_rotation(Quat::from_rotation_z(FRAC_PI_2)),
+        );
+        let turret =
+            Transform::from_xyz(1., 0., 0.).with_rotation(Quat::from_rotation_z(FRAC_PI_4));
+
+        let g_transform = current_global_transform(&ship, &turret);
+        assert!(
+            g_transform
+                .translation()
+                .xy()
+                .abs_diff_eq(Vec2::new(5., 1.), 1e-5)
+        );
+        let angle = rotation::quat_to_vec2(g_transform.rotation()).to_angle();
+        assert!((angle - 3. * FRAC_PI_4).abs() < 1e-5, "{angle}");
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::{
+    math::bounding::{Aabb2d, BoundingVolume},
+    prelude::*,
+};
+
+/// deeper than this, bodies share a leaf instead of splitting further
+/// (i.e., bodies sitting on top of each other)
+const MAX_DEPTH: usize = 32;
+
+/// body pulling on others: entity, position, mass
+#[derive(Clone, Copy, Debug)]
+pub struct GravityBody {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub mass: f32,
+}
+
+/// one square of the quadtree, knowing the total mass and center of mass of everything in it
+#[derive(Debug)]
+struct Node {
+    bounds: Aabb2d,
+    mass: f32,
+    center_of_mass: Vec2,
+    /// index of first of the 4 child nodes, None while a leaf
+    children: Option<usize>,
+    /// only filled in leaves
+    bodies: Vec<GravityBody>,
+}
+
+impl Node {
+    fn new(bounds: Aabb2d) -> Self {
+        Node {
+            bounds,
+            mass: 0.,
+            center_of_mass: Vec2::ZERO,
+            children: None,
+            bodies: Vec::new(),
+        }
+    }
+
+    /// which quadrant of the node the position falls in
+    fn quadrant(&self, position: Vec2) -> usize {
+        let center = self.bounds.center();
+        usize::from(position.x >= center.x) + 2 * usize::from(position.y >= center.y)
+    }
+}
+
+/// Barnes-Hut quadtree, groups far away bodies together so each body only needs
+/// O(log n) pulls instead of one from every other body
+#[derive(Debug)]
+pub struct QuadTree {
+    nodes: Vec<Node>,
+    /// how far a group must be (width / distance) before it's treated as one body
+    /// 0 is exact, higher is faster but rougher
+    theta: f32,
+    /// added to the distance, so bodies very close together don't pull infinitely hard
+    softening: f32,
+}
+
+impl QuadTree {
+    pub fn new(bodies: &[GravityBody], theta: f32, softening: f32) -> Self {
+        // square bounds around every body, so quadrants stay square
+        let (min, max) = bodies.iter().fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), body| (min.min(body.position), max.max(body.position)),
+        );
+        let bounds = if bodies.is_empty() {
+            Aabb2d::new(Vec2::ZERO, Vec2::ONE)
+        } else {
+            let half_size = ((max - min).max_element() / 2.).max(1.);
+            Aabb2d::new((min + max) / 2., Vec2::splat(half_size))
+        };
+
+        let mut tree = QuadTree {
+            nodes: vec![Node::new(bounds)],
+            theta,
+            softening,
+        };
+        for body in bodies {
+            tree.insert(0, *body, 0);
+        }
+        tree.summarize(0);
+        tree
+    }
+
+    fn insert(&mut self, index: usize, body: GravityBody, depth: usize) {
+        if let Some(first_child) = self.nodes[index].children {
+            let quadrant = self.nodes[index].quadrant(body.position);
+            self.insert(first_child + quadrant, body, depth + 1);
+            return;
+        }
+
+        if self.nodes[index].bodies.is_empty() || depth >= MAX_DEPTH {
+            self.nodes[index].bodies.push(body);
+            return;
+        }
+
+        // leaf already taken, split it and push both bodies down
+        self.split(index);
+        let bodies = std::mem::take(&mut self.nodes[index].bodies);
+        for existing in bodies {
+            self.insert(index, existing, depth);
+        }
+        self.insert(index, body, depth);
+    }
+
+    fn split(&mut self, index: usize) {
+        let bounds = self.nodes[index].bounds;
+        let center = bounds.center();
+        let quarter = bounds.half_size() / 2.;
+
+        let first_child = self.nodes.len();
+        for quadrant in 0..4 {
+            let offset = Vec2::new(
+                if quadrant % 2 == 1 {
+                    quarter.x
+                } else {
+                    -quarter.x
+                },
+                if quadrant / 2 == 1 {
+                    quarter.y
+                } else {
+                    -quarter.y
+                },
+            );
+            self.nodes
+                .push(Node::new(Aabb2d::new(center + offset, quarter)));
+        }
+        self.nodes[index].children = Some(first_child);
+    }
+
+    /// fill in mass and center of mass of every node, from the leaves up
+    fn summarize(&mut self, index: usize) -> (f32, Vec2) {
+        let (mass, weighted_position) = match self.nodes[index].children {
+            Some(first_child) => (first_child..first_child + 4)
+                .map(|child| self.summarize(child))
+                .fold(
+                    (0., Vec2::ZERO),
+                    |(mass, weighted), (child_mass, child_com)| {
+                        (mass + child_mass, weighted + child_com * child_mass)
+                    },
+                ),
+            None => self.nodes[index]
+                .bodies
+                .iter()
+                .fold((0., Vec2::ZERO), |(mass, weighted), body| {
+                    (mass + body.mass, weighted + body.position * body.mass)
+                }),
+        };
+
+        let center_of_mass = if mass > 0. {
+            weighted_position / mass
+        } else {
+            self.nodes[index].bounds.center()
+        };
+        self.nodes[index].mass = mass;
+        self.nodes[index].center_of_mass = center_of_mass;
+        (mass, center_of_mass)
+    }
+
+    /// pull at the point from everything in the tree, not counting the excluded entity
+    /// multiply by the gravity constant to get acceleration
+    pub fn field(&self, point: Vec2, exclude: Option<Entity>) -> Vec2 {
+        let mut field = Vec2::ZERO;
+        let mut stack = vec![0];
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if node.mass <= 0. {
+                continue;
+            }
+
+            let Some(first_child) = node.children else {
+                for body in &node.bodies {
+                    if Some(body.entity) != exclude {
+                        field += self.pull(point, body.position, body.mass);
+                    }
+                }
+                continue;
+            };
+
+            // node holding the point might hold the excluded body, so always look inside it
+            let width = node.bounds.half_size().x * 2.;
+            let distance = point.distance(node.center_of_mass);
+            let contains_point = node.bounds.closest_point(point) == point;
+            if !contains_point && width < self.theta * distance {
+                // far enough away to act like one big body
+                field += self.pull(point, node.center_of_mass, node.mass);
+            } else {
+                stack.extend(first_child..first_child + 4);
+            }
+        }
+        field
+    }
+
+    /// softened pull towards a mass
+    fn pull(&self, point: Vec2, position: Vec2, mass: f32) -> Vec2 {
+        let offset = position - point;
+        let distance_squared = offset.length_squared() + self.softening.powi(2);
+        if distance_squared <= 0. {
+            return Vec2::ZERO;
+        }
+        offset * mass / (distance_squared * distance_squared.sqrt())
+    }
+}
@@ -1,6 +1,9 @@
-use super::mass::Mass;
+use super::{
+    barnes_hut::{GravityBody, QuadTree},
+    mass::Mass,
+};
 use crate::{
-    SystemUpdateSet, data_config::global_settings::GlobalSettings, debug, velocity::Velocity,
+    SystemUpdateSet, data_config::global_settings::GlobalSettings, velocity::force::Force,
 };
 use bevy::prelude::*;
 
@@ -8,7 +11,7 @@ pub struct GravityPlugin {}
 
 impl Plugin for GravityPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (apply_gravity).in_set(SystemUpdateSet::Main));
+        app.add_systems(FixedUpdate, (apply_gravity).in_set(SystemUpdateSet::Main));
     }
 }
 
@@ -20,37 +23,39 @@ pub struct Gravitated {}
 #[derive(Component)]
 pub struct GravitySource {}
 
+/// pull every Gravitated entity towards every GravitySource, using a Barnes-Hut quadtree so
+/// far away groups of sources are treated as one
 fn apply_gravity(
-    mut param_set: ParamSet<(
-        Query<(&Transform, &Mass), With<GravitySource>>, // sources
-        Query<(&Transform, &mut Velocity), With<Gravitated>>, // affected
-    )>,
+    source_query: Query<(Entity, &Transform, &Mass), With<GravitySource>>,
+    affected_query: Query<(Entity, &Transform, &mut Force, Option<&Mass>), With<Gravitated>>,
     g_settings: Res<GlobalSettings>,
-    time: Res<Time>,
 ) {
-    let sources: Vec<(Transform, f32)> = param_set
-        .p0()
-        .iter()
-        .map(|(transform, mass)| (*transform, mass.0))
-        .collect();
-
-    for (source_transform, source_mass) in &sources {
-        for (affected_transform, mut affected_velocity) in &mut param_set.p1() {
-            if source_transform == affected_transform {
-                // same object
-                continue;
-            }
-
-            // compute acceleration of affected (mass cancels out)
-            let acceleration = g_settings.gravity_const * (source_mass)
-                / source_transform
-                    .translation
-                    .distance_squared(affected_transform.translation);
+    let tree = gravity_tree(&source_query, &g_settings);
 
-            // point acceleration vector to source, then add to affected velocity
-            let dir = (source_transform.translation.xy() - affected_transform.translation.xy())
-                .normalize_or(Vec2::new(0., 0.));
-            affected_velocity.0 += dir.xy() * acceleration * time.delta_secs();
-        }
+    for (entity, transform, mut force, opt_mass) in affected_query {
+        // entities without mass act like they weigh 1
+        let mass = opt_mass.map_or(1., |mass| mass.0);
+        let field = tree.field(transform.translation.xy(), Some(entity));
+        force.0 += field * g_settings.gravity_const * mass;
     }
 }
+
+/// quadtree of every GravitySource
+pub fn gravity_tree(
+    source_query: &Query<(Entity, &Transform, &Mass), With<GravitySource>>,
+    g_settings: &GlobalSettings,
+) -> QuadTree {
+    let bodies: Vec<GravityBody> = source_query
+        .iter()
+        .map(|(entity, transform, mass)| GravityBody {
+            entity,
+            position: transform.translation.xy(),
+            mass: mass.0,
+        })
+        .collect();
+    QuadTree::new(
+        &bodies,
+        g_settings.gravity_theta,
+        g_settings.gravity_softening,
+    )
+}
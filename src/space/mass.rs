@@ -1,10 +1,121 @@
+use crate::{SystemUpdateSet, collision::collider::Collider, health::Killed};
 use bevy::prelude::*;
+use serde::Deserialize;
 
 pub struct MassPlugin {}
 
 impl Plugin for MassPlugin {
-    fn build(&self, app: &mut App) {}
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_composite_mass.in_set(SystemUpdateSet::Main))
+            .add_observer(composite_mass_constructor)
+            .register_type::<Mass>()
+            .register_type::<CompositeMass>()
+            .register_type::<CenterOfMass>();
+    }
 }
 
-#[derive(Component)]
+#[derive(Component, Reflect, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub struct Mass(pub f32);
+
+/// moment of inertia, how hard the entity is to spin
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct Inertia(pub f32);
+
+/// local position mass is balanced around
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+pub struct CenterOfMass(pub Vec2);
+
+/// Mass, Inertia, and CenterOfMass are summed up from the entity itself (the hull) and every
+/// child with Mass that hasn't been killed (i.e., ships losing parts get lighter)
+#[derive(Component, Reflect, Debug, Clone, Default)]
+pub struct CompositeMass {
+    /// mass of the entity without any children, taken from its Mass when added
+    hull: f32,
+}
+
+/// remember mass the entity started with, as Mass gets replaced by the total
+fn composite_mass_constructor(
+    trigger: Trigger<OnAdd, CompositeMass>,
+    mut query: Query<(&mut CompositeMass, Option<&Mass>)>,
+) {
+    let Ok((mut composite, opt_mass)) = query.get_mut(trigger.target()) else {
+        return;
+    };
+    composite.hull = opt_mass.map_or(0., |mass| mass.0);
+}
+
+/// keep Mass, CenterOfMass, and Inertia of composite entities up to date with their children
+fn update_composite_mass(
+    composite_query: Query<(
+        Entity,
+        &CompositeMass,
+        Option<&Children>,
+        Option<&Collider>,
+        Option<&Mass>,
+        Option<&CenterOfMass>,
+        Option<&Inertia>,
+    )>,
+    part_query: Query<(&Mass, &Transform, Option<&Collider>), Without<Killed>>,
+    mut commands: Commands,
+) {
+    for (entity, composite, opt_children, opt_collider, opt_mass, opt_com, opt_inertia) in
+        composite_query
+    {
+        // (mass, local position, inertia around own center)
+        let mut parts = vec![(
+            composite.hull,
+            Vec2::ZERO,
+            part_inertia(composite.hull, opt_collider),
+        )];
+        parts.extend(
+            opt_children
+                .into_iter()
+                .flatten()
+                .filter_map(|child| part_query.get(*child).ok())
+                .map(|(mass, transform, opt_collider)| {
+                    (
+                        mass.0,
+                        transform.translation.xy(),
+                        part_inertia(mass.0, opt_collider),
+                    )
+                }),
+        );
+
+        let total_mass: f32 = parts.iter().map(|(mass, _, _)| mass).sum();
+        if total_mass <= 0. {
+            continue;
+        }
+        let center_of_mass = parts
+            .iter()
+            .map(|(mass, position, _)| *position * *mass)
+            .sum::<Vec2>()
+            / total_mass;
+        // parallel axis theorem: spinning around the center of mass instead of own center
+        let inertia: f32 = parts
+            .iter()
+            .map(|(mass, position, inertia)| {
+                inertia + mass * position.distance_squared(center_of_mass)
+            })
+            .sum();
+
+        let (mass, center_of_mass, inertia) = (
+            Mass(total_mass),
+            CenterOfMass(center_of_mass),
+            Inertia(inertia),
+        );
+        if opt_mass != Some(&mass)
+            || opt_com != Some(&center_of_mass)
+            || opt_inertia != Some(&inertia)
+        {
+            commands
+                .entity(entity)
+                .insert((mass, center_of_mass, inertia));
+        }
+    }
+}
+
+/// inertia of a single part around its own center, from the shape of its collider
+/// parts without colliders are treated as points
+fn part_inertia(mass: f32, opt_collider: Option<&Collider>) -> f32 {
+    opt_collider.map_or(0., |collider| collider.bounding.moment_of_inertia(mass))
+}
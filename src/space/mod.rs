@@ -1,7 +1,10 @@
 use bevy::prelude::*;
-mod gravity;
+pub mod barnes_hut;
+pub mod gravity;
 pub mod mass;
+pub mod orbit;
 mod planet;
+pub mod trajectory;
 
 pub struct SpacePlugin {}
 
@@ -11,6 +14,8 @@ impl Plugin for SpacePlugin {
             planet::PlanetPlugin {},
             mass::MassPlugin {},
             gravity::GravityPlugin {},
+            orbit::OrbitPlugin {},
+            trajectory::TrajectoryPlugin {},
         ));
     }
 }
@@ -0,0 +1,116 @@
+use super::mass::Mass;
+use crate::{data_config::global_settings::GlobalSettings, rotation, velocity::Velocity};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+pub struct OrbitPlugin {}
+
+impl Plugin for OrbitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(place_in_orbit)
+            .register_type::<OrbitAround>();
+    }
+}
+
+/// shape of an orbit, and where along it the body starts
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub struct OrbitalElements {
+    /// half the longest width of the orbit (radius, if circular)
+    pub semi_major_axis: f32,
+    /// 0 is a circle, closer to 1 is a longer ellipse
+    #[serde(default)]
+    pub eccentricity: f32,
+    /// how far around the orbit the body starts, measured from periapsis, in degrees
+    #[serde(default)]
+    pub phase: f32,
+    /// direction periapsis (closest point) points in, in degrees
+    #[serde(default)]
+    pub periapsis_angle: f32,
+    #[serde(default)]
+    pub clockwise: bool,
+}
+
+impl OrbitalElements {
+    pub fn circular(radius: f32, phase: f32) -> Self {
+        OrbitalElements {
+            semi_major_axis: radius,
+            eccentricity: 0.,
+            phase,
+            periapsis_angle: 0.,
+            clockwise: false,
+        }
+    }
+
+    /// position and velocity relative to the parent
+    /// `gravity_param` is gravity_const * (parent mass + body mass)
+    pub fn relative_state(&self, gravity_param: f32) -> (Vec2, Vec2) {
+        // closed orbits only
+        let eccentricity = self.eccentricity.clamp(0., 0.99);
+        let semi_latus_rectum = self.semi_major_axis * (1. - eccentricity.powi(2));
+        let anomaly = self.phase.to_radians();
+
+        let distance = semi_latus_rectum / (1. + eccentricity * anomaly.cos());
+        let mut position = rotation::rad_to_vec2(anomaly) * distance;
+
+        // same speed as vis-viva (v^2 = mu * (2 / r - 1 / a)), but also gives the direction
+        let speed_scale = (gravity_param / semi_latus_rectum).max(0.).sqrt();
+        let mut velocity = Vec2::new(-anomaly.sin(), eccentricity + anomaly.cos()) * speed_scale;
+
+        if self.clockwise {
+            // mirror orbit across the periapsis line
+            position.y = -position.y;
+            velocity.y = -velocity.y;
+        }
+
+        let periapsis_rotation = Rot2::degrees(self.periapsis_angle);
+        (periapsis_rotation * position, periapsis_rotation * velocity)
+    }
+}
+
+/// puts the entity into orbit around `parent` when added, setting its Transform and Velocity
+/// parent needs a Transform and Mass
+#[derive(Component, Reflect, Debug, Clone)]
+pub struct OrbitAround {
+    pub parent: Entity,
+    pub elements: OrbitalElements,
+}
+
+impl OrbitAround {
+    pub fn new(parent: Entity, elements: OrbitalElements) -> Self {
+        OrbitAround { parent, elements }
+    }
+}
+
+fn place_in_orbit(
+    trigger: Trigger<OnAdd, OrbitAround>,
+    orbit_query: Query<&OrbitAround>,
+    mut body_query: Query<(&mut Transform, Option<&mut Velocity>, Option<&Mass>)>,
+    g_settings: Res<GlobalSettings>,
+) {
+    let entity = trigger.target();
+    let Ok(orbit) = orbit_query.get(entity) else {
+        return;
+    };
+
+    let Ok((p_transform, p_opt_velocity, Some(p_mass))) = body_query.get(orbit.parent) else {
+        warn!("orbit parent has no Transform or Mass! Cannot place body in orbit.");
+        return;
+    };
+    let p_position = p_transform.translation;
+    let p_velocity = p_opt_velocity.map_or(Vec2::ZERO, |velocity| velocity.0);
+    let p_mass = p_mass.0;
+
+    let Ok((mut transform, opt_velocity, opt_mass)) = body_query.get_mut(entity) else {
+        return;
+    };
+    let mass = opt_mass.map_or(0., |mass| mass.0);
+
+    let (relative_position, relative_velocity) = orbit
+        .elements
+        .relative_state(g_settings.gravity_const * (p_mass + mass));
+
+    transform.translation = p_position + relative_position.extend(0.);
+    if let Some(mut velocity) = opt_velocity {
+        velocity.0 = p_velocity + relative_velocity;
+    }
+}
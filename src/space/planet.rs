@@ -1,53 +1,265 @@
 use crate::{
-    AppState, Health,
+    AppState, Health, SystemUpdateSet,
     collision::{
-        collider::{Collider, CollisionLayer},
+        collider::{Collider, CollisionLayer, CollisionResponse},
         collider_type::ColliderType,
+        lifecycle::{self, CollisionStarted},
+        response,
     },
     color_palette::PalColor,
+    data_config::star_system::{CelestialBody, StarSystem},
+    debug,
     graphic::Graphic,
+    health::Killed,
     space::{
         gravity::{Gravitated, GravitySource},
         mass::Mass,
+        orbit::OrbitAround,
     },
     velocity::Velocity,
 };
-use bevy::prelude::*;
+use bevy::{platform::collections::HashSet, prelude::*};
+use std::f32::consts::TAU;
 
 pub struct PlanetPlugin {}
 
 impl Plugin for PlanetPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::GameReady), setup);
+        app.add_systems(OnEnter(AppState::GameReady), setup)
+            .add_systems(
+                Update,
+                (
+                    // before impacts bounce the planets, so merges use the speed they hit at
+                    planet_impacts
+                        .after(lifecycle::track_collision_pairs)
+                        .before(response::resolve_collisions),
+                    destroy_killed_planets,
+                )
+                    .in_set(SystemUpdateSet::Main),
+            );
+
+        debug::insert_inspectable_resource::<PlanetImpactSettings>(app, None, false);
     }
 }
 
-fn make_planet(transform: Transform, mass: Mass, velocity: Velocity) -> impl Bundle {
+#[derive(Component)]
+pub struct Planet {}
+
+/// tweakable settings for what happens when planets hit each other
+#[derive(Resource, Reflect, Debug, Clone)]
+pub struct PlanetImpactSettings {
+    /// planets hitting slower than this merge, faster shatter into debris
+    pub fragment_speed: f32,
+    /// pieces each shattered planet breaks into
+    pub fragment_count: usize,
+    /// debris lighter than this is vaporized instead of spawned
+    pub min_fragment_mass: f32,
+    /// how much of the impact speed debris flies outwards with
+    pub fragment_kick: f32,
+    /// speed debris flies outwards with when a planet is destroyed by damage
+    pub destroyed_kick: f32,
+}
+
+impl Default for PlanetImpactSettings {
+    fn default() -> Self {
+        PlanetImpactSettings {
+            fragment_speed: 30.,
+            fragment_count: 4,
+            min_fragment_mass: 1.,
+            fragment_kick: 0.5,
+            destroyed_kick: 5.,
+        }
+    }
+}
+
+fn make_planet(name: &str, transform: Transform, mass: Mass, velocity: Velocity) -> impl Bundle {
     let radius = determine_radius(&mass);
-    let graphic = Graphic::new(Circle::new(radius).into(), PalColor::Random.into());
+    let graphic = Graphic::new(Circle::new(radius).into(), PalColor::Random);
     (
-        Name::new("Planet"),
+        Name::new(name.to_owned()),
+        Planet {},
         transform,
         velocity.clone(),
+        mass,
         GravitySource {},
         Gravitated {},
         graphic,
-        Collider::new(ColliderType::new_circle(radius), CollisionLayer::Planet),
+        planet_collider(radius),
         Health::new(1000.),
     )
 }
 
-fn setup(mut commands: Commands) {
-    commands.spawn(make_planet(
-        Transform::from_translation(Vec3::new(-50., 0., 0.)),
-        Mass(10.),
-        Velocity(Vec2::new(0., -10.)),
-    ));
-    commands.spawn(make_planet(
-        Transform::from_translation(Vec3::new(100., 0., 0.)),
-        Mass(100.),
-        Velocity(Vec2::new(-10., 0.)),
+fn planet_collider(radius: f32) -> Collider {
+    Collider::new(ColliderType::new_circle(radius), CollisionLayer::PLANET)
+        .with_response(CollisionResponse::new(0.3, 0.5))
+}
+
+fn setup(star_system: Res<StarSystem>, mut commands: Commands) {
+    for body in &star_system.bodies {
+        spawn_body(body, None, &mut commands);
+    }
+}
+
+/// spawn body, then everything orbiting it
+fn spawn_body(body: &CelestialBody, parent: Option<Entity>, commands: &mut Commands) {
+    let mut entity = commands.spawn(make_planet(
+        &body.name,
+        Transform::from_translation(body.position.extend(0.)),
+        Mass(body.mass),
+        Velocity(body.velocity),
     ));
+
+    if let Some(atmosphere) = body.atmosphere {
+        entity.insert(atmosphere);
+    }
+
+    match (parent, &body.orbit) {
+        (Some(parent), Some(orbit)) => {
+            entity.insert(OrbitAround::new(parent, orbit.clone()));
+        }
+        (Some(_), None) => {
+            warn!("{} is a satellite without an orbit!", body.name);
+        }
+        (None, Some(_)) => {
+            warn!("{} has an orbit, but nothing to orbit!", body.name);
+        }
+        (None, None) => {}
+    }
+
+    let entity = entity.id();
+    for satellite in &body.satellites {
+        spawn_body(satellite, Some(entity), commands);
+    }
+}
+
+/// planets that hit each other slowly merge into one, planets that hit fast shatter
+fn planet_impacts(
+    mut events: EventReader<CollisionStarted>,
+    mut planet_query: Query<(&Transform, &Velocity, &Mass, &mut Graphic), With<Planet>>,
+    settings: Res<PlanetImpactSettings>,
+    mut commands: Commands,
+) {
+    // planets already merged or shattered this frame, their components are out of date
+    let mut handled = HashSet::new();
+
+    for event in events.read() {
+        let (entity1, entity2, contact) = (event.0, event.1, event.2);
+        if handled.contains(&entity1) || handled.contains(&entity2) {
+            continue;
+        }
+        let Ok(
+            [
+                (transform1, velocity1, mass1, _),
+                (transform2, velocity2, mass2, _),
+            ],
+        ) = planet_query.get_many([entity1, entity2])
+        else {
+            continue;
+        };
+        handled.insert(entity1);
+        handled.insert(entity2);
+
+        let total_mass = mass1.0 + mass2.0;
+        if total_mass <= 0. {
+            continue;
+        }
+        // momentum is conserved either way
+        let center_of_mass = (transform1.translation.xy() * mass1.0
+            + transform2.translation.xy() * mass2.0)
+            / total_mass;
+        let velocity = (velocity1.0 * mass1.0 + velocity2.0 * mass2.0) / total_mass;
+        let impact_speed = velocity1.0.distance(velocity2.0);
+
+        if impact_speed > settings.fragment_speed {
+            info!("planets shattered at speed {}", impact_speed);
+            commands.entity(entity1).despawn();
+            commands.entity(entity2).despawn();
+            shatter(
+                contact.point,
+                velocity,
+                total_mass,
+                settings.fragment_count * 2,
+                impact_speed * settings.fragment_kick,
+                &settings,
+                &mut commands,
+            );
+            continue;
+        }
+
+        // heavier planet swallows the lighter one
+        let (survivor, swallowed, rotation) = if mass1.0 >= mass2.0 {
+            (entity1, entity2, transform1.rotation)
+        } else {
+            (entity2, entity1, transform2.rotation)
+        };
+        info!("planets merged");
+
+        let mass = Mass(total_mass);
+        let radius = determine_radius(&mass);
+        if let Ok((_, _, _, mut graphic)) = planet_query.get_mut(survivor) {
+            graphic.replace_shape(Circle::new(radius).into());
+        }
+        commands.entity(swallowed).despawn();
+        commands.entity(survivor).insert((
+            Transform::from_translation(center_of_mass.extend(0.)).with_rotation(rotation),
+            Velocity(velocity),
+            mass,
+            planet_collider(radius),
+        ));
+    }
+}
+
+/// planets with no health left shatter
+fn destroy_killed_planets(
+    query: Query<(Entity, &Transform, &Velocity, &Mass), (With<Planet>, Added<Killed>)>,
+    settings: Res<PlanetImpactSettings>,
+    mut commands: Commands,
+) {
+    for (entity, transform, velocity, mass) in query {
+        info!("planet destroyed");
+        commands.entity(entity).despawn();
+        shatter(
+            transform.translation.xy(),
+            velocity.0,
+            mass.0,
+            settings.fragment_count,
+            settings.destroyed_kick,
+            &settings,
+            &mut commands,
+        );
+    }
+}
+
+/// spread mass between pieces flying outwards from the center, spaced so they don't touch
+fn shatter(
+    center: Vec2,
+    velocity: Vec2,
+    mass: f32,
+    count: usize,
+    kick: f32,
+    settings: &PlanetImpactSettings,
+    commands: &mut Commands,
+) {
+    let piece_mass = Mass(mass / count.max(1) as f32);
+    if piece_mass.0 < settings.min_fragment_mass {
+        // too small to be worth keeping
+        return;
+    }
+
+    let piece_radius = determine_radius(&piece_mass);
+    let original_radius = determine_radius(&Mass(mass));
+    // far enough apart that neighboring pieces don't overlap
+    let spread = original_radius + piece_radius / (TAU / (2. * count as f32)).sin().max(0.1);
+
+    for i in 0..count {
+        let direction = Vec2::from_angle(TAU * i as f32 / count as f32);
+        commands.spawn(make_planet(
+            "Debris",
+            Transform::from_translation((center + direction * spread).extend(0.)),
+            piece_mass,
+            Velocity(velocity + direction * kick),
+        ));
+    }
 }
 
 /// radius determined by mass
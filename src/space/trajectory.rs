@@ -0,0 +1,300 @@
+use super::{
+    barnes_hut::{GravityBody, QuadTree},
+    gravity::{Gravitated, GravitySource},
+    mass::Mass,
+};
+use crate::{
+    SystemUpdateSet,
+    collision::collider::Collider,
+    color_palette::PalColor,
+    data_config::global_settings::GlobalSettings,
+    debug,
+    player::Player,
+    velocity::{
+        Velocity,
+        damping::{Damping, DragField},
+    },
+};
+use bevy::prelude::*;
+
+pub struct TrajectoryPlugin {}
+
+impl Plugin for TrajectoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            show_player_trajectory
+                .run_if(|settings: Res<TrajectorySettings>| settings.enabled)
+                .in_set(SystemUpdateSet::Main),
+        );
+
+        debug::insert_inspectable_resource::<TrajectorySettings>(app, None, false);
+    }
+}
+
+/// tweakable settings of the player's predicted path
+#[derive(Resource, Reflect, Debug, Clone)]
+pub struct TrajectorySettings {
+    pub enabled: bool,
+    /// how far ahead to predict, in seconds
+    pub duration: f32,
+    /// seconds simulated per step, smaller is more accurate but slower
+    pub step: f32,
+}
+
+impl Default for TrajectorySettings {
+    fn default() -> Self {
+        TrajectorySettings {
+            enabled: false,
+            duration: 20.,
+            step: 0.05,
+        }
+    }
+}
+
+/// gravity source as it moves through the prediction
+struct PredictedSource {
+    body: GravityBody,
+    velocity: Vec2,
+    /// pulled by other sources, or just drifts
+    gravitated: bool,
+    /// anything closer than this has hit it
+    radius: f32,
+    /// own linear damping, before any drag fields
+    damping: f32,
+}
+
+/// drag field, centered on a gravity source if it's on one so it moves along with it
+struct PredictedField {
+    entity: Entity,
+    field: DragField,
+    /// center for fields that aren't on a gravity source
+    position: Vec2,
+}
+
+/// where the player is going if engines stay off
+#[derive(Default)]
+struct Trajectory {
+    path: Vec<Vec2>,
+    /// closest and furthest points from the body pulling hardest at the start
+    periapsis: Option<Vec2>,
+    apoapsis: Option<Vec2>,
+    /// where path runs into a gravity source, which also ends the path
+    impact: Option<Vec2>,
+}
+
+/// runs gravity and damping forward from the player's current velocity, and draws the result
+fn show_player_trajectory(
+    player_query: Query<(&Transform, &Velocity, Option<&Damping>), With<Player>>,
+    source_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Mass,
+            Option<&Velocity>,
+            Option<&Collider>,
+            Option<&Damping>,
+            Has<Gravitated>,
+        ),
+        With<GravitySource>,
+    >,
+    field_query: Query<(Entity, &DragField, &GlobalTransform)>,
+    settings: Res<TrajectorySettings>,
+    g_settings: Res<GlobalSettings>,
+    mut gizmo: Gizmos,
+) {
+    let Ok((p_transform, p_velocity, p_opt_damping)) = player_query.single() else {
+        return;
+    };
+    let linear_damping = |opt_damping: Option<&Damping>| {
+        opt_damping.map_or(g_settings.linear_damping, |damping| damping.linear)
+    };
+
+    let sources: Vec<PredictedSource> = source_query
+        .iter()
+        .map(
+            |(entity, transform, mass, opt_velocity, opt_collider, opt_damping, gravitated)| {
+                PredictedSource {
+                    body: GravityBody {
+                        entity,
+                        position: transform.translation.xy(),
+                        mass: mass.0,
+                    },
+                    velocity: opt_velocity.map_or(Vec2::ZERO, |velocity| velocity.0),
+                    gravitated,
+                    radius: opt_collider.map_or(0., |collider| collider.bounding.bounding_radius()),
+                    damping: linear_damping(opt_damping),
+                }
+            },
+        )
+        .collect();
+    let fields: Vec<PredictedField> = field_query
+        .iter()
+        .map(|(entity, field, f_g_transform)| PredictedField {
+            entity,
+            field: *field,
+            position: f_g_transform.translation().xy(),
+        })
+        .collect();
+
+    let trajectory = predict(
+        p_transform.translation.xy(),
+        p_velocity.0,
+        linear_damping(p_opt_damping),
+        sources,
+        &fields,
+        &settings,
+        &g_settings,
+    );
+
+    gizmo.linestrip_2d(trajectory.path.iter().copied(), PalColor::Blue);
+    if let Some(periapsis) = trajectory.periapsis {
+        gizmo.circle_2d(Isometry2d::from_translation(periapsis), 3., PalColor::Green);
+    }
+    if let Some(apoapsis) = trajectory.apoapsis {
+        gizmo.circle_2d(Isometry2d::from_translation(apoapsis), 3., PalColor::White);
+    }
+    if let Some(impact) = trajectory.impact {
+        gizmo.circle_2d(Isometry2d::from_translation(impact), 5., PalColor::Red);
+    }
+}
+
+/// linear damping at `position`, own damping plus every drag field it's in (except its own)
+fn damping_at(
+    own_damping: f32,
+    position: Vec2,
+    entity: Option<Entity>,
+    fields: &[PredictedField],
+    sources: &[PredictedSource],
+) -> f32 {
+    let drag: f32 = fields
+        .iter()
+        .filter(|field| Some(field.entity) != entity)
+        .map(|field| {
+            let center = sources
+                .iter()
+                .find(|source| source.body.entity == field.entity)
+                .map_or(field.position, |source| source.body.position);
+            field.field.drag_at(position.distance(center)).0
+        })
+        .sum();
+    own_damping + drag
+}
+
+/// steps the same gravity and damping model physics uses (semi-implicit Euler), moving sources
+/// as well
+fn predict(
+    start: Vec2,
+    velocity: Vec2,
+    damping: f32,
+    mut sources: Vec<PredictedSource>,
+    fields: &[PredictedField],
+    settings: &TrajectorySettings,
+    g_settings: &GlobalSettings,
+) -> Trajectory {
+    let steps = (settings.duration / settings.step.max(f32::EPSILON)).ceil() as usize;
+    let (mut position, mut velocity) = (start, velocity);
+
+    // body pulling hardest at the start is the one being orbited
+    let orbited = sources
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| {
+            let pull = |source: &PredictedSource| {
+                source.body.mass
+                    / source
+                        .body
+                        .position
+                        .distance_squared(start)
+                        .max(f32::EPSILON)
+            };
+            pull(a).total_cmp(&pull(b))
+        })
+        .map(|(index, _)| index);
+
+    let mut trajectory = Trajectory {
+        path: vec![start],
+        ..default()
+    };
+    // distance from orbited body at every point on the path
+    let mut distances = Vec::new();
+
+    for _ in 0..steps {
+        let bodies: Vec<GravityBody> = sources.iter().map(|source| source.body).collect();
+        let tree = QuadTree::new(
+            &bodies,
+            g_settings.gravity_theta,
+            g_settings.gravity_softening,
+        );
+
+        // damping is worked out from where everything was at the start of the step, same as
+        // physics
+        let source_damping: Vec<f32> = sources
+            .iter()
+            .map(|source| {
+                damping_at(
+                    source.damping,
+                    source.body.position,
+                    Some(source.body.entity),
+                    fields,
+                    &sources,
+                )
+            })
+            .collect();
+        let player_damping = damping_at(damping, position, None, fields, &sources);
+
+        for (source, linear) in sources.iter_mut().zip(source_damping) {
+            if source.gravitated {
+                let field = tree.field(source.body.position, Some(source.body.entity));
+                source.velocity += field * g_settings.gravity_const * settings.step;
+            }
+            source.velocity *= decay(linear, settings.step);
+            source.body.position += source.velocity * settings.step;
+        }
+
+        velocity += tree.field(position, None) * g_settings.gravity_const * settings.step;
+        velocity *= decay(player_damping, settings.step);
+        position += velocity * settings.step;
+        trajectory.path.push(position);
+
+        if let Some(orbited) = orbited {
+            distances.push((position, position.distance(sources[orbited].body.position)));
+        }
+
+        if let Some(hit) = sources
+            .iter()
+            .find(|source| position.distance(source.body.position) <= source.radius)
+        {
+            // put impact on the surface, not wherever the step ended up
+            let from_center = (position - hit.body.position).normalize_or_zero();
+            trajectory.impact = Some(hit.body.position + from_center * hit.radius);
+            break;
+        }
+    }
+
+    // only counts as periapsis or apoapsis if it turns around, not if path just ends there
+    let turning_point = |closest: bool| {
+        let (index, _) = distances.iter().enumerate().min_by(|(_, a), (_, b)| {
+            if closest {
+                a.1.total_cmp(&b.1)
+            } else {
+                b.1.total_cmp(&a.1)
+            }
+        })?;
+        (index > 0 && index + 1 < distances.len()).then(|| distances[index].0)
+    };
+    trajectory.periapsis = turning_point(true);
+    trajectory.apoapsis = turning_point(false);
+
+    trajectory
+}
+
+/// how much of its velocity a body keeps over `step` with the given damping, same as
+/// `apply_damping`
+fn decay(linear: f32, step: f32) -> f32 {
+    if linear > 0. {
+        (-linear * step).exp()
+    } else {
+        1.
+    }
+}
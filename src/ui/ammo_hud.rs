@@ -0,0 +1,83 @@
+use crate::{
+    AppState, SystemUpdateSet,
+    color_palette::PalColor,
+    player::Player,
+    ship_composition::{
+        ammo::{AmmoStore, ReloadState},
+        gun::Gun,
+    },
+};
+use bevy::prelude::*;
+
+pub struct AmmoHudPlugin {}
+
+impl Plugin for AmmoHudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::GameReady), spawn_ammo_hud)
+            .add_systems(Update, update_ammo_hud.in_set(SystemUpdateSet::Main));
+    }
+}
+
+/// text listing ammo of each player gun that has a magazine
+#[derive(Component)]
+struct AmmoHud {}
+
+fn spawn_ammo_hud(mut commands: Commands) {
+    commands.spawn((
+        AmmoHud {},
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(5.0),
+            left: Val::Px(5.0),
+            ..default()
+        },
+        Text::new(""),
+        TextColor(PalColor::White.into()),
+    ));
+}
+
+/// one line per gun: loaded / magazine size, and what's left to reload with
+fn update_ammo_hud(
+    mut hud_query: Query<&mut Text, With<AmmoHud>>,
+    player_query: Query<(&Children, Option<&AmmoStore>), With<Player>>,
+    gun_query: Query<(&Gun, Option<&Name>)>,
+) {
+    let Ok(mut text) = hud_query.single_mut() else {
+        return;
+    };
+    let Ok((p_children, opt_store)) = player_query.single() else {
+        text.0.clear();
+        return;
+    };
+
+    let mut lines = Vec::new();
+    for &child in p_children {
+        let Ok((gun, opt_name)) = gun_query.get(child) else {
+            continue;
+        };
+        let Some(magazine) = gun.magazine() else {
+            continue;
+        };
+
+        let name = opt_name.map_or("gun", |name| name.as_str());
+        let reserve = match (magazine.shared(), opt_store) {
+            (true, Some(store)) => Some(store.rounds),
+            _ => magazine.reserve(),
+        }
+        .map_or("inf".to_owned(), |rounds| rounds.to_string());
+
+        lines.push(match gun.reload_state() {
+            ReloadState::Ready => format!(
+                "{name}: {}/{} ({reserve})",
+                magazine.loaded(),
+                magazine.size()
+            ),
+            ReloadState::Reloading(progress) => {
+                format!("{name}: reloading {:.0}% ({reserve})", progress * 100.)
+            }
+            ReloadState::Empty => format!("{name}: empty ({reserve})"),
+        });
+    }
+
+    text.0 = lines.join("\n");
+}
@@ -1,10 +1,11 @@
 use bevy::prelude::*;
+mod ammo_hud;
 mod test;
 
 pub struct UiPlugin {}
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(test::TestPlugin {});
+        app.add_plugins((test::TestPlugin {}, ammo_hud::AmmoHudPlugin {}));
     }
 }
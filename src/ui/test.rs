@@ -26,7 +26,7 @@ fn spawn_box(mut commands: Commands) {
     commands.spawn((container, children![(square, background_color)]));
 }
 
-fn spawn_text_in_ui(mut commands: Commands, assets: Res<AssetServer>) {
+fn spawn_text_in_ui(mut commands: Commands) {
     commands.spawn((
         Node {
             position_type: PositionType::Absolute,
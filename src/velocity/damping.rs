@@ -0,0 +1,95 @@
+use super::{AngularVelocity, Velocity};
+use crate::{SystemUpdateSet, data_config::global_settings::GlobalSettings};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+pub struct DampingPlugin {}
+
+impl Plugin for DampingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            apply_damping
+                .in_set(SystemUpdateSet::Early)
+                .after(super::force::apply_forces)
+                .after(super::force::apply_torques)
+                .before(super::update_velocity)
+                .before(super::update_angular_velocity),
+        )
+        .register_type::<Damping>()
+        .register_type::<DragField>();
+    }
+}
+
+/// how quickly entity slows down on its own, replaces the defaults in GlobalSettings
+/// each is the fraction of speed lost per second (roughly, for small values)
+#[derive(Component, Reflect, Deserialize, Debug, Clone, Copy)]
+pub struct Damping {
+    pub linear: f32,
+    pub angular: f32,
+}
+
+/// slows down anything inside the radius (i.e., planet atmospheres, nebulae)
+/// strongest at the center, fading out to nothing at the edge
+#[derive(Component, Reflect, Deserialize, Debug, Clone, Copy)]
+pub struct DragField {
+    pub radius: f32,
+    pub linear: f32,
+    pub angular: f32,
+}
+
+impl DragField {
+    /// (linear, angular) drag at the given distance from the center
+    pub fn drag_at(&self, distance: f32) -> (f32, f32) {
+        if self.radius <= 0. || distance >= self.radius {
+            return (0., 0.);
+        }
+        let strength = 1. - distance / self.radius;
+        (self.linear * strength, self.angular * strength)
+    }
+}
+
+/// slow down top level entities by their damping, plus any drag fields they're in
+/// children move with their parent, so they don't need slowing down
+fn apply_damping(
+    query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Velocity,
+            &mut AngularVelocity,
+            Option<&Damping>,
+        ),
+        Without<ChildOf>,
+    >,
+    field_query: Query<(Entity, &DragField, &GlobalTransform)>,
+    g_settings: Res<GlobalSettings>,
+    time: Res<Time>,
+) {
+    for (entity, transform, mut velocity, mut angular_velocity, opt_damping) in query {
+        let (mut linear, mut angular) = opt_damping.map_or(
+            (g_settings.linear_damping, g_settings.angular_damping),
+            |damping| (damping.linear, damping.angular),
+        );
+
+        let position = transform.translation.xy();
+        for (field_entity, field, f_g_transform) in &field_query {
+            if field_entity == entity {
+                // planets don't drag on themselves
+                continue;
+            }
+            let (field_linear, field_angular) =
+                field.drag_at(position.distance(f_g_transform.translation().xy()));
+            linear += field_linear;
+            angular += field_angular;
+        }
+
+        // exponential decay, so large damping slows to a stop instead of reversing
+        if linear > 0. {
+            velocity.0 *= (-linear * time.delta_secs()).exp();
+        }
+        if angular > 0. {
+            angular_velocity.0 *= (-angular * time.delta_secs()).exp();
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use super::{AngularVelocity, Velocity};
+use crate::{
+    SystemUpdateSet,
+    space::mass::{Inertia, Mass},
+};
+use bevy::prelude::*;
+
+pub struct ForcePlugin {}
+
+impl Plugin for ForcePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (apply_forces, apply_torques)
+                .in_set(SystemUpdateSet::Early)
+                .before(super::update_velocity)
+                .before(super::update_angular_velocity),
+        )
+        .register_type::<Force>()
+        .register_type::<Torque>();
+    }
+}
+
+/// sum of every push on the entity this fixed step (i.e., engines, gravity, collisions)
+/// cleared after being applied to Velocity
+#[derive(Clone, Component, Default, Reflect)]
+pub struct Force(pub Vec2);
+
+/// sum of every twist on the entity this fixed step, cleared after being applied to
+/// AngularVelocity
+#[derive(Clone, Component, Default, Reflect)]
+pub struct Torque(pub f32);
+
+/// turn accumulated force into velocity, heavier entities speed up slower
+/// entities without Mass act like they weigh 1
+pub(super) fn apply_forces(
+    query: Query<(&mut Force, &mut Velocity, Option<&Mass>)>,
+    time: Res<Time>,
+) {
+    for (mut force, mut velocity, opt_mass) in query {
+        if force.0 == Vec2::ZERO {
+            continue;
+        }
+        let mass = opt_mass.map_or(1., |mass| mass.0);
+        if mass > 0. {
+            velocity.0 += force.0 / mass * time.delta_secs();
+        }
+        force.0 = Vec2::ZERO;
+    }
+}
+
+/// turn accumulated torque into angular velocity, entities with more inertia spin up slower
+/// entities without Inertia act like they have 1
+pub(super) fn apply_torques(
+    query: Query<(&mut Torque, &mut AngularVelocity, Option<&Inertia>)>,
+    time: Res<Time>,
+) {
+    for (mut torque, mut angular_velocity, opt_inertia) in query {
+        if torque.0 == 0. {
+            continue;
+        }
+        let inertia = opt_inertia.map_or(1., |inertia| inertia.0);
+        if inertia > 0. {
+            angular_velocity.0 += torque.0 / inertia * time.delta_secs();
+        }
+        torque.0 = 0.;
+    }
+}
@@ -1,8 +1,7 @@
+use super::{AngularVelocity, Velocity};
 use crate::{
     SystemUpdateSet,
-    record::{Record, record_fixed_update, record_update},
-    rotation,
-    schedule::UpdateSchedule,
+    record::{record_fixed_update, record_update},
 };
 use bevy::prelude::*;
 
@@ -12,57 +11,195 @@ impl Plugin for GlobalPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             FixedUpdate,
-            (
-                record_fixed_update::<GlobalTransform>,
-                update_g_velocity,
-                update_g_angular_velocity,
-            )
-                .in_set(SystemUpdateSet::Early),
+            record_fixed_update::<GlobalTransform>.in_set(SystemUpdateSet::Early),
         )
         .add_systems(
             Update,
-            (record_update::<GlobalTransform>, record_update::<Transform>)
+            (
+                record_update::<GlobalTransform>,
+                record_update::<Transform>,
+                propagate_global_velocity,
+            )
                 .in_set(SystemUpdateSet::Early),
         )
-        .register_type::<GlobalVelocity>();
+        .register_type::<GlobalVelocity>()
+        .register_type::<GlobalAngularVelocity>();
     }
 }
 
+/// velocity in world space, including what's inherited from parents
 /// read only
 #[derive(Component, serde::Deserialize, Clone, Debug, Default, Reflect)]
 pub struct GlobalVelocity(pub Vec2);
 
-/// get difference between last 2 positions to get velocity
-fn update_g_velocity(
-    query: Query<(&mut GlobalVelocity, &Record<GlobalTransform>)>,
-    time: Res<Time>,
+/// angular velocity in world space, including what's inherited from parents
+/// read only
+#[derive(Component, Default, Reflect)]
+pub struct GlobalAngularVelocity(pub f32);
+
+/// world space motion of a parent, passed down to its children
+#[derive(Clone, Copy)]
+struct ParentMotion {
+    velocity: Vec2,
+    angular_velocity: f32,
+    position: Vec2,
+    rotation: Quat,
+}
+
+/// velocity of a point fixed to the parent (at `world_offset` from the parent, already rotated
+/// into world space) that is also moving on its own at `local_velocity` in the parent's space
+/// v = v_parent + w_parent x offset + R_parent * v_local
+fn child_velocity(parent: &ParentMotion, world_offset: Vec2, local_velocity: Vec2) -> Vec2 {
+    parent.velocity
+        + world_offset.perp() * parent.angular_velocity
+        + (parent.rotation * local_velocity.extend(0.)).xy()
+}
+
+/// walk down from every top level entity, adding each parent's motion to its children
+fn propagate_global_velocity(
+    root_query: Query<Entity, (With<Velocity>, Without<ChildOf>)>,
+    mut query: Query<(
+        &Velocity,
+        &AngularVelocity,
+        &GlobalTransform,
+        &mut GlobalVelocity,
+        &mut GlobalAngularVelocity,
+        Option<&Children>,
+    )>,
 ) {
-    for (mut g_velocity, transform_record) in query {
-        let records = transform_record.newest_number(2, &UpdateSchedule::Update);
+    let mut stack: Vec<(Entity, Option<ParentMotion>)> =
+        root_query.iter().map(|root| (root, None)).collect();
+
+    while let Some((entity, opt_parent)) = stack.pop() {
+        let Ok((
+            velocity,
+            angular_velocity,
+            g_transform,
+            mut g_velocity,
+            mut g_angular_velocity,
+            opt_children,
+        )) = query.get_mut(entity)
+        else {
+            continue;
+        };
 
-        let transform_1 = records[0];
-        let transform_2 = records.get(1).map_or(transform_1, |t| *t);
+        let position = g_transform.translation().xy();
+        (g_velocity.0, g_angular_velocity.0) = match opt_parent {
+            Some(parent) => (
+                child_velocity(&parent, position - parent.position, velocity.0),
+                parent.angular_velocity + angular_velocity.0,
+            ),
+            None => (velocity.0, angular_velocity.0),
+        };
 
-        let delta_pos = transform_1.val.translation().xy() - transform_2.val.translation().xy();
-        g_velocity.0 = delta_pos / time.delta_secs();
+        let motion = ParentMotion {
+            velocity: g_velocity.0,
+            angular_velocity: g_angular_velocity.0,
+            position,
+            rotation: g_transform.rotation(),
+        };
+        for child in opt_children.into_iter().flatten() {
+            stack.push((*child, Some(motion)));
+        }
     }
 }
 
-/// wrapper of GlobalTransform
-#[derive(Component, Default, Reflect)]
-pub struct GlobalAngularVelocity(pub f32);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::f32::consts::FRAC_PI_2;
 
-/// get difference between last 2 positions to get velocity
-fn update_g_angular_velocity(
-    query: Query<(&mut GlobalAngularVelocity, &Record<GlobalTransform>)>,
-    time: Res<Time>,
-) {
-    for (mut g_velocity, rotation_record) in query {
-        let records = rotation_record.newest_number(2, &UpdateSchedule::Update);
+    fn spawn_body(
+        world: &mut World,
+        position: Vec2,
+        velocity: Vec2,
+        angular_velocity: f32,
+        opt_parent: Option<Entity>,
+    ) -> Entity {
+        let mut entity = world.spawn((
+            Velocity(velocity),
+            AngularVelocity(angular_velocity),
+            GlobalTransform::from_translation(position.extend(0.)),
+        ));
+        if let Some(parent) = opt_parent {
+            entity.insert(ChildOf(parent));
+        }
+        entity.id()
+    }
+
+    fn propagate(world: &mut World) {
+        world
+            .run_system_once(propagate_global_velocity)
+            .expect("system should run");
+    }
+
+    fn global_velocity(world: &World, entity: Entity) -> (Vec2, f32) {
+        (
+            world.get::<GlobalVelocity>(entity).unwrap().0,
+            world.get::<GlobalAngularVelocity>(entity).unwrap().0,
+        )
+    }
+
+    #[test]
+    fn child_of_translating_parent() {
+        let mut world = World::new();
+        let ship = spawn_body(&mut world, Vec2::ZERO, Vec2::new(10., 0.), 0., None);
+        let gun = spawn_body(&mut world, Vec2::new(5., 0.), Vec2::ZERO, 0., Some(ship));
+
+        propagate(&mut world);
+
+        assert_eq!(global_velocity(&world, ship), (Vec2::new(10., 0.), 0.));
+        assert_eq!(global_velocity(&world, gun), (Vec2::new(10., 0.), 0.));
+    }
+
+    #[test]
+    fn child_of_rotating_parent() {
+        let mut world = World::new();
+        let ship = spawn_body(&mut world, Vec2::ZERO, Vec2::ZERO, 2., None);
+        let gun = spawn_body(&mut world, Vec2::new(0., 3.), Vec2::ZERO, 0., Some(ship));
+
+        propagate(&mut world);
+
+        // w x r, spinning counter clockwise swings the top of the ship left
+        let (velocity, angular_velocity) = global_velocity(&world, gun);
+        assert!(velocity.abs_diff_eq(Vec2::new(-6., 0.), 1e-5), "{velocity}");
+        assert_eq!(angular_velocity, 2.);
+    }
+
+    #[test]
+    fn nested_children() {
+        let mut world = World::new();
+        let ship = spawn_body(&mut world, Vec2::ZERO, Vec2::new(1., 0.), 1., None);
+        let turret = spawn_body(&mut world, Vec2::new(2., 0.), Vec2::ZERO, 0.5, Some(ship));
+        let barrel = spawn_body(&mut world, Vec2::new(2., 1.), Vec2::ZERO, 0., Some(turret));
+
+        propagate(&mut world);
+
+        let (velocity, angular_velocity) = global_velocity(&world, turret);
+        assert!(velocity.abs_diff_eq(Vec2::new(1., 2.), 1e-5), "{velocity}");
+        assert_eq!(angular_velocity, 1.5);
+
+        // turret's extra spin adds on top of the ship's
+        let (velocity, angular_velocity) = global_velocity(&world, barrel);
+        assert!(
+            velocity.abs_diff_eq(Vec2::new(-0.5, 2.), 1e-5),
+            "{velocity}"
+        );
+        assert_eq!(angular_velocity, 1.5);
+    }
 
-        let delta_velocity = rotation::quat_to_vec2(records[0].val.rotation())
-            - rotation::quat_to_vec2(records.get(1).unwrap_or(&records[0]).val.rotation());
+    #[test]
+    fn local_velocity_follows_parent_rotation() {
+        let parent = ParentMotion {
+            velocity: Vec2::new(1., 0.),
+            angular_velocity: 0.,
+            position: Vec2::ZERO,
+            rotation: Quat::from_rotation_z(FRAC_PI_2),
+        };
 
-        g_velocity.0 = (delta_velocity / time.delta_secs()).to_angle();
+        // moving along the parent's x axis, which points up in world space
+        let velocity = child_velocity(&parent, Vec2::new(0., 1.), Vec2::new(2., 0.));
+        assert!(velocity.abs_diff_eq(Vec2::new(1., 2.), 1e-5), "{velocity}");
     }
 }
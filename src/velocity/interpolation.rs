@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+
+pub struct InterpolationPlugin {}
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedFirst, restore_physics_transform)
+            .add_systems(FixedLast, store_physics_transform)
+            .add_systems(
+                RunFixedMainLoop,
+                interpolate_transform.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop),
+            )
+            .register_type::<TransformInterpolation>();
+    }
+}
+
+/// smooths Transform between fixed physics steps
+/// physics only sees `current`, everything else sees a Transform somewhere between `previous`
+/// and `current`
+#[derive(Component, Clone, Default, Reflect)]
+pub struct TransformInterpolation {
+    /// physics Transform before the last fixed step
+    previous: Transform,
+    /// physics Transform after the last fixed step
+    current: Transform,
+    /// what was last written to Transform, None until first seen
+    rendered: Option<Transform>,
+}
+
+impl TransformInterpolation {
+    /// moves physics state along with any edits made to Transform outside of physics
+    /// (i.e., collision pushes, teleports), so they aren't undone by the next fixed step
+    fn absorb_edits(&mut self, transform: &Transform) {
+        let Some(rendered) = self.rendered else {
+            self.previous = *transform;
+            self.current = *transform;
+            self.rendered = Some(*transform);
+            return;
+        };
+
+        let translation_edit = transform.translation - rendered.translation;
+        let rotation_edit = transform.rotation * rendered.rotation.inverse();
+
+        for physics in [&mut self.previous, &mut self.current] {
+            physics.translation += translation_edit;
+            physics.rotation = (rotation_edit * physics.rotation).normalize();
+            physics.scale = transform.scale;
+        }
+        self.rendered = Some(*transform);
+    }
+}
+
+/// put Transform back to where physics left it, so fixed step starts from the real state
+fn restore_physics_transform(query: Query<(&mut TransformInterpolation, &mut Transform)>) {
+    for (mut interpolation, mut transform) in query {
+        interpolation.absorb_edits(&transform);
+        interpolation.previous = interpolation.current;
+        transform.set_if_neq(interpolation.current);
+    }
+}
+
+/// remember where physics moved Transform to
+fn store_physics_transform(query: Query<(&mut TransformInterpolation, &Transform)>) {
+    for (mut interpolation, transform) in query {
+        interpolation.current = *transform;
+        interpolation.rendered = Some(*transform);
+    }
+}
+
+/// show Transform part way between the last two fixed steps, based on how much time is left
+/// over until the next one
+fn interpolate_transform(
+    query: Query<(&mut TransformInterpolation, &mut Transform)>,
+    time: Res<Time<Fixed>>,
+) {
+    let fraction = time.overstep_fraction();
+
+    for (mut interpolation, mut transform) in query {
+        interpolation.absorb_edits(&transform);
+
+        let (previous, current) = (interpolation.previous, interpolation.current);
+        transform.set_if_neq(Transform {
+            translation: previous.translation.lerp(current.translation, fraction),
+            rotation: previous.rotation.slerp(current.rotation, fraction),
+            scale: transform.scale,
+        });
+
+        interpolation.rendered = Some(*transform);
+    }
+}
@@ -7,14 +7,17 @@ pub struct MaxPlugin {}
 impl Plugin for MaxPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
+            FixedUpdate,
+            (max_velocity, max_angular_velocity)
+                .in_set(SystemUpdateSet::Early)
+                .after(super::force::apply_forces)
+                .after(super::force::apply_torques)
+                .before(super::update_velocity)
+                .before(super::update_angular_velocity),
+        )
+        .add_systems(
             Update,
-            (
-                max_velocity,
-                max_angular_velocity,
-                add_max_to_velocity,
-                add_max_to_angular_velocity,
-            )
-                .in_set(SystemUpdateSet::Early),
+            (add_max_to_velocity, add_max_to_angular_velocity).in_set(SystemUpdateSet::Early),
         )
         .register_type::<MaxVelocity>()
         .register_type::<MaxAngularVelocity>();
@@ -1,6 +1,9 @@
 use crate::{SystemUpdateSet, rotation};
 use bevy::prelude::*;
+pub mod damping;
+pub mod force;
 pub mod global;
+pub mod interpolation;
 pub mod max;
 
 pub struct VelocityPlugin {}
@@ -8,20 +11,31 @@ pub struct VelocityPlugin {}
 impl Plugin for VelocityPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
+            FixedUpdate,
             (update_velocity, update_angular_velocity).in_set(SystemUpdateSet::Early),
         )
         .add_observer(add_velocity_to_transform)
         .add_observer(add_angular_velocity_to_transform)
         .register_type::<Velocity>()
         .register_type::<AngularVelocity>()
-        .add_plugins((global::GlobalPlugin {}, max::MaxPlugin {}));
+        .add_plugins((
+            damping::DampingPlugin {},
+            force::ForcePlugin {},
+            global::GlobalPlugin {},
+            interpolation::InterpolationPlugin {},
+            max::MaxPlugin {},
+        ));
     }
 }
 
 /// NOTE: may need GlobalTransform and GlobalVelocity if child
 #[derive(Clone, Component, Default, Reflect)]
-#[require(Transform, global::GlobalVelocity)]
+#[require(
+    Transform,
+    global::GlobalVelocity,
+    interpolation::TransformInterpolation,
+    force::Force
+)]
 pub struct Velocity(pub Vec2);
 
 impl Velocity {
@@ -29,10 +43,12 @@ impl Velocity {
 }
 
 #[derive(Clone, Component, Default, Reflect)]
-#[require(Transform, global::GlobalAngularVelocity)]
+#[require(Transform, global::GlobalAngularVelocity, force::Torque)]
 pub struct AngularVelocity(pub f32);
 
 /// update transform posiiton equal to computed body transform
+/// runs after velocity has been changed for this fixed step (semi-implicit Euler), which keeps
+/// orbits stable
 fn update_velocity(query: Query<(&Velocity, &mut Transform)>, time: Res<Time>) {
     for (velocity, mut transform) in query {
         transform.translation.x += velocity.0.x * time.delta_secs();